@@ -1,5 +1,5 @@
 use std::fmt::{self, Display, Formatter};
-use std::num::NonZeroUsize;
+use std::num::{NonZeroU32, NonZeroUsize};
 use std::ops::RangeInclusive;
 use std::path::PathBuf;
 use std::str::FromStr;
@@ -13,6 +13,14 @@ use semver::Version;
 /// in environment variables.
 const ENV_PATH_SEP: char = if cfg!(windows) { ';' } else { ':' };
 
+/// The default for `--max-layout-iterations`.
+///
+/// This must be the same as `typst::MAX_ITERATIONS`, which is not available
+/// here because this file is also compiled by the build script. The CLI checks
+/// at compile time that the two agree.
+pub const DEFAULT_MAX_LAYOUT_ITERATIONS: NonZeroUsize =
+    NonZeroUsize::MIN.saturating_add(4);
+
 /// The Typst compiler.
 #[derive(Debug, Clone, Parser)]
 #[clap(name = "typst", version = crate::typst_version(), author)]
@@ -56,6 +64,9 @@ pub enum Command {
     /// Processes an input file to extract provided metadata
     Query(QueryCommand),
 
+    /// Counts the words, characters, and pages of a document
+    Stats(StatsCommand),
+
     /// Lists all discovered fonts in system and custom font paths
     Fonts(FontsCommand),
 
@@ -76,6 +87,7 @@ pub struct CompileCommand {
     /// a page number template must be present if the source document renders to multiple pages.
     /// Use `{p}` for page numbers, `{0p}` for zero padded page numbers, `{t}` for page count.
     /// For example, `doc-page-{0p}-of-{t}.png` creates `doc-page-01-of-10.png` and so on.
+    /// PPM images of multiple pages can also be streamed to stdout one after another.
     #[clap(required_if_eq("input", "-"), value_parser = ValueParser::new(output_value_parser))]
     pub output: Option<Output>,
 
@@ -91,10 +103,28 @@ pub struct CompileCommand {
     #[arg(long = "pages", value_delimiter = ',')]
     pub pages: Option<Vec<PageRangeArgument>>,
 
+    /// Splits a PDF into one file per chapter, starting a new file at every
+    /// top-level heading
+    ///
+    /// The output path must contain a chapter number template (`{p}` or
+    /// `{0p}`), e.g. `book-{0p}.pdf`. Page numbers continue across the files
+    /// and links between them keep working in PDF viewers that support it.
+    #[arg(long = "split-chapters", conflicts_with = "pages")]
+    pub split_chapters: bool,
+
     /// Output a Makefile rule describing the current compilation
     #[clap(long = "make-deps", value_name = "PATH")]
     pub make_deps: Option<PathBuf>,
 
+    /// Writes the files the compilation depended on (sources, images, data,
+    /// and fonts) to the given path
+    #[clap(long = "deps", value_name = "PATH")]
+    pub deps: Option<PathBuf>,
+
+    /// The format to write the dependencies in
+    #[arg(long = "deps-format", value_enum, default_value_t = DepsFormat::Json)]
+    pub deps_format: DepsFormat,
+
     /// The format of the output file, inferred from the extension by default
     #[arg(long = "format", short = 'f')]
     pub format: Option<OutputFormat>,
@@ -104,10 +134,37 @@ pub struct CompileCommand {
     #[arg(long = "open")]
     pub open: Option<Option<String>>,
 
-    /// The PPI (pixels per inch) to use for PNG export
+    /// The PPI (pixels per inch) to use for PNG and PPM export
     #[arg(long = "ppi", default_value_t = 144.0)]
     pub ppi: f32,
 
+    /// How many samples to take per pixel along each axis for PNG and PPM
+    /// export. Higher values give smoother edges, but take longer
+    #[arg(long = "supersampling", value_name = "FACTOR", default_value_t = NonZeroU32::MIN)]
+    pub supersampling: NonZeroU32,
+
+    /// Averages supersampled pixels in sRGB space instead of in linear light
+    #[arg(long = "no-gamma-correction")]
+    pub no_gamma_correction: bool,
+
+    /// Draws text that is smaller than this many pixels as bars in PNG and
+    /// PPM export, which is faster for thumbnails. Zero disables this
+    #[arg(long = "text-placeholder-below", value_name = "PIXELS", default_value_t = 0)]
+    pub text_placeholder_below: u32,
+
+    /// Transforms all colors in the output, e.g. to produce a printer-friendly
+    /// grayscale version of a document
+    #[arg(long = "colors", value_name = "TRANSFORM")]
+    pub colors: Option<ColorTransform>,
+
+    /// Arranges multiple pages on each output sheet, e.g. to print a booklet
+    #[arg(long = "impose", value_name = "LAYOUT")]
+    pub impose: Option<Imposition>,
+
+    /// Adds crop marks around each page, enlarging it to make room for them
+    #[arg(long = "crop-marks")]
+    pub crop_marks: bool,
+
     /// Produces performance timings of the compilation process (experimental)
     ///
     /// The resulting JSON file can be loaded into a tracing tool such as
@@ -155,6 +212,19 @@ pub struct QueryCommand {
     pub format: SerializationFormat,
 }
 
+/// Counts the words, characters, and pages of a document
+#[derive(Debug, Clone, Parser)]
+pub struct StatsCommand {
+    /// Shared arguments
+    #[clap(flatten)]
+    pub common: SharedArgs,
+
+    /// The format to serialize in, instead of printing a human-readable
+    /// summary
+    #[clap(long = "format")]
+    pub format: Option<SerializationFormat>,
+}
+
 // Output file format for query command
 #[derive(Debug, Copy, Clone, Eq, PartialEq, ValueEnum)]
 pub enum SerializationFormat {
@@ -182,14 +252,22 @@ pub struct SharedArgs {
     )]
     pub inputs: Vec<(String, String)>,
 
-    /// Adds additional directories to search for fonts
+    /// Converts raw blocks in a language to SVG with an external command
+    ///
+    /// The command is split at whitespace and run without a shell. It
+    /// receives the raw text on stdin and must write an SVG image to stdout.
+    /// For example: `--raw-converter dot="dot -Tsvg"`.
     #[clap(
-        long = "font-path",
-        env = "TYPST_FONT_PATHS",
-        value_name = "DIR",
-        value_delimiter = ENV_PATH_SEP,
+        long = "raw-converter",
+        value_name = "lang=command",
+        action = ArgAction::Append,
+        value_parser = ValueParser::new(parse_input_pair),
     )]
-    pub font_paths: Vec<PathBuf>,
+    pub raw_converters: Vec<(String, String)>,
+
+    /// Arguments related to fonts.
+    #[clap(flatten)]
+    pub font_args: FontArgs,
 
     /// The document's creation date formatted as a UNIX timestamp.
     ///
@@ -209,6 +287,32 @@ pub struct SharedArgs {
         value_parser = clap::value_parser!(DiagnosticFormat)
     )]
     pub diagnostic_format: DiagnosticFormat,
+
+    /// How often to lay out the document at most until introspection converges
+    #[clap(
+        long = "max-layout-iterations",
+        value_name = "COUNT",
+        default_value_t = DEFAULT_MAX_LAYOUT_ITERATIONS
+    )]
+    pub max_layout_iterations: NonZeroUsize,
+
+    /// Turns warnings with the given code (like `missing-glyph`) into errors
+    ///
+    /// Pass a class like `overflow` to deny all warnings in it, or `all` to
+    /// deny all warnings. The code of a warning is shown next to its severity.
+    /// A rule for a specific code takes precedence over one for its class,
+    /// which takes precedence over `all`. If a code is both allowed and
+    /// denied, it is denied.
+    #[clap(long = "deny-warning", value_name = "CODE", action = ArgAction::Append)]
+    pub deny_warnings: Vec<String>,
+
+    /// Suppresses warnings with the given code
+    ///
+    /// Pass a class like `overflow` to suppress all warnings in it, or `all`
+    /// to suppress all warnings. A rule for a specific code takes precedence
+    /// over one for its class, which takes precedence over `all`.
+    #[clap(long = "allow-warning", value_name = "CODE", action = ArgAction::Append)]
+    pub allow_warnings: Vec<String>,
 }
 
 /// Parses a UNIX timestamp according to <https://reproducible-builds.org/specs/source-date-epoch/>
@@ -338,6 +442,22 @@ fn parse_page_number(value: &str) -> Result<NonZeroUsize, &'static str> {
 /// Lists all discovered fonts in system and custom font paths
 #[derive(Debug, Clone, Parser)]
 pub struct FontsCommand {
+    /// Arguments related to fonts.
+    #[clap(flatten)]
+    pub font_args: FontArgs,
+
+    /// Also lists style variants of each font family and their coverage
+    #[arg(long)]
+    pub variants: bool,
+
+    /// Only lists font families that cover all characters of the given text
+    #[arg(long, value_name = "TEXT")]
+    pub covering: Option<String>,
+}
+
+/// Common arguments related to font discovery.
+#[derive(Debug, Clone, Args)]
+pub struct FontArgs {
     /// Adds additional directories to search for fonts
     #[clap(
         long = "font-path",
@@ -347,9 +467,23 @@ pub struct FontsCommand {
     )]
     pub font_paths: Vec<PathBuf>,
 
-    /// Also lists style variants of each font family
-    #[arg(long)]
-    pub variants: bool,
+    /// Ensures system fonts won't be searched, unless explicitly included via
+    /// `--font-path`
+    #[arg(long, env = "TYPST_IGNORE_SYSTEM_FONTS")]
+    pub ignore_system_fonts: bool,
+
+    /// Ensures fonts embedded into the Typst binary won't be used
+    #[arg(long, env = "TYPST_IGNORE_EMBEDDED_FONTS")]
+    pub ignore_embedded_fonts: bool,
+}
+
+/// Which format to use for the dependency list.
+#[derive(Debug, Copy, Clone, Eq, PartialEq, ValueEnum)]
+pub enum DepsFormat {
+    /// A JSON object with the inputs and outputs of the compilation.
+    Json,
+    /// A Makefile rule, as with `--make-deps`.
+    Make,
 }
 
 /// Which format to use for diagnostics.
@@ -357,6 +491,8 @@ pub struct FontsCommand {
 pub enum DiagnosticFormat {
     Human,
     Short,
+    /// One JSON object per line, for editors and CI.
+    Json,
 }
 
 impl Display for DiagnosticFormat {
@@ -384,12 +520,45 @@ pub struct UpdateCommand {
     pub revert: bool,
 }
 
+/// A transformation applied to all colors of the output.
+#[derive(Debug, Copy, Clone, Eq, PartialEq, ValueEnum)]
+pub enum ColorTransform {
+    /// Converts all colors to shades of gray.
+    Grayscale,
+    /// Converts all colors to CMYK, restricting them to a print gamut.
+    Cmyk,
+}
+
+/// How to arrange pages on printed sheets.
+#[derive(Debug, Copy, Clone, Eq, PartialEq, ValueEnum)]
+pub enum Imposition {
+    /// Places two consecutive pages side by side on each sheet.
+    #[value(name = "2-up")]
+    TwoUp,
+    /// Places four consecutive pages in a two-by-two grid on each sheet.
+    #[value(name = "4-up")]
+    FourUp,
+    /// Reorders pages into folded, double-sided sheets that are stacked into
+    /// a booklet.
+    Booklet,
+}
+
 /// Which format to use for the generated output file.
 #[derive(Debug, Copy, Clone, Eq, PartialEq, Ord, PartialOrd, ValueEnum)]
 pub enum OutputFormat {
     Pdf,
     Png,
     Svg,
+    Ppm,
+    Txt,
+    Md,
+}
+
+impl OutputFormat {
+    /// Whether exporting to this format needs the pixels of raster images.
+    pub fn decodes_images(&self) -> bool {
+        matches!(self, Self::Pdf | Self::Png | Self::Ppm)
+    }
 }
 
 impl Display for OutputFormat {
@@ -1,25 +1,34 @@
 use std::fs::{self, File};
 use std::io::{self, Write};
+use std::num::NonZeroUsize;
 use std::path::{Path, PathBuf};
 
 use chrono::{Datelike, Timelike};
 use codespan_reporting::diagnostic::{Diagnostic, Label};
 use codespan_reporting::term;
-use ecow::{eco_format, EcoString};
+use ecow::{eco_format, EcoString, EcoVec};
 use parking_lot::RwLock;
 use rayon::iter::{IntoParallelRefIterator, ParallelIterator};
-use typst::diag::{bail, At, Severity, SourceDiagnostic, StrResult};
+use serde::Serialize;
+use typst::diag::{
+    bail, warning, At, Severity, SourceDiagnostic, SourceResult, StrResult,
+    WarningAction, WarningPolicy,
+};
 use typst::eval::Tracer;
-use typst::foundations::{Datetime, Smart};
+use typst::foundations::{Datetime, NativeElement, Smart, StyleChain};
 use typst::layout::{Frame, PageRanges};
-use typst::model::Document;
+use typst::model::{Document, HeadingElem};
 use typst::syntax::{FileId, Source, Span};
 use typst::visualize::Color;
 use typst::{World, WorldExt};
+use typst_render::RenderOptions;
 
 use crate::args::{
-    CompileCommand, DiagnosticFormat, Input, Output, OutputFormat, PageRangeArgument,
+    ColorTransform, CompileCommand, DepsFormat, DiagnosticFormat, Input, Output,
+    OutputFormat, PageRangeArgument, SharedArgs, DEFAULT_MAX_LAYOUT_ITERATIONS,
 };
+use crate::impose;
+use crate::text::{self, TextFlavor};
 use crate::timings::Timer;
 use crate::watch::Status;
 use crate::world::SystemWorld;
@@ -28,6 +37,9 @@ use crate::{set_failed, terminal};
 type CodespanResult<T> = Result<T, CodespanError>;
 type CodespanError = codespan_reporting::files::Error;
 
+// The CLI's default can't refer to the library's constant directly.
+const _: () = assert!(DEFAULT_MAX_LAYOUT_ITERATIONS.get() == typst::MAX_ITERATIONS.get());
+
 impl CompileCommand {
     /// The output path.
     pub fn output(&self) -> Output {
@@ -40,6 +52,9 @@ impl CompileCommand {
                     OutputFormat::Pdf => "pdf",
                     OutputFormat::Png => "png",
                     OutputFormat::Svg => "svg",
+                    OutputFormat::Ppm => "ppm",
+                    OutputFormat::Txt => "txt",
+                    OutputFormat::Md => "md",
                 },
             ))
         })
@@ -56,6 +71,9 @@ impl CompileCommand {
                 Some(ext) if ext.eq_ignore_ascii_case("pdf") => OutputFormat::Pdf,
                 Some(ext) if ext.eq_ignore_ascii_case("png") => OutputFormat::Png,
                 Some(ext) if ext.eq_ignore_ascii_case("svg") => OutputFormat::Svg,
+                Some(ext) if ext.eq_ignore_ascii_case("ppm") => OutputFormat::Ppm,
+                Some(ext) if ext.eq_ignore_ascii_case("txt") => OutputFormat::Txt,
+                Some(ext) if ext.eq_ignore_ascii_case("md") => OutputFormat::Md,
                 _ => bail!("could not infer output format for path {}.\nconsider providing the format manually with `--format/-f`", output.display()),
             }
         } else {
@@ -73,6 +91,15 @@ impl CompileCommand {
             )
         })
     }
+
+    /// The quality options for PNG and PPM export.
+    fn render_options(&self) -> RenderOptions {
+        RenderOptions {
+            supersampling: self.supersampling,
+            gamma_correct: !self.no_gamma_correction,
+            text_placeholder_below: self.text_placeholder_below,
+        }
+    }
 }
 
 /// Execute a compilation command.
@@ -111,12 +138,24 @@ pub fn compile_once(
     }
 
     let mut tracer = Tracer::new();
-    let result = typst::compile(world, &mut tracer);
-    let warnings = tracer.warnings();
+    let result =
+        typst::compile_with(world, &mut tracer, command.common.max_layout_iterations);
+    let mut warnings = tracer.warnings();
+    if let Ok(document) = &result {
+        warnings.extend(missing_glyph_warnings(document));
+    }
+    let result = apply_warning_policy(&command.common, result, &mut warnings)
+        .and_then(|document| check_images(&document, command).map(|()| document));
 
     match result {
         // Export the PDF / PNG.
-        Ok(document) => {
+        Ok(mut document) => {
+            if let Some(transform) = command.colors {
+                transform_colors(&mut document, transform);
+            }
+
+            impose::impose(&mut document, command.impose, command.crop_marks);
+
             export(world, &document, command, watching)?;
             let duration = start.elapsed();
 
@@ -131,7 +170,7 @@ pub fn compile_once(
             print_diagnostics(world, &[], &warnings, command.common.diagnostic_format)
                 .map_err(|err| eco_format!("failed to print diagnostics ({err})"))?;
 
-            write_make_deps(world, command)?;
+            write_deps(world, command)?;
 
             if let Some(open) = command.open.take() {
                 if let Output::Path(file) = command.output() {
@@ -161,6 +200,87 @@ pub fn compile_once(
     Ok(())
 }
 
+/// Fail if the export format needs the pixels of a raster image that turned
+/// out to be corrupt. Images are decoded lazily, so this only surfaces now.
+fn check_images(document: &Document, command: &CompileCommand) -> SourceResult<()> {
+    if command.output_format().is_ok_and(|format| format.decodes_images()) {
+        for page in &document.pages {
+            page.frame.check_images()?;
+        }
+    }
+    Ok(())
+}
+
+/// Warn about characters that were rendered as tofus because no available font
+/// contains them.
+fn missing_glyph_warnings(document: &Document) -> Vec<SourceDiagnostic> {
+    document
+        .missing_glyphs()
+        .into_iter()
+        .map(|missing| {
+            let times = if missing.count == 1 { "time" } else { "times" };
+            warning!(
+                missing.span,
+                "no font could display the character {:?} (used {} {times})",
+                missing.c, missing.count;
+                hint: "try adding a font that supports the {} script", missing.script,
+            )
+            .with_code("missing-glyph")
+        })
+        .collect()
+}
+
+/// Suppresses warnings or turns them into errors as requested on the command
+/// line.
+pub fn apply_warning_policy<T>(
+    args: &SharedArgs,
+    result: SourceResult<T>,
+    warnings: &mut EcoVec<SourceDiagnostic>,
+) -> SourceResult<T> {
+    // Denials are applied last so that they win over allowances of the same
+    // code, independently of the order of the arguments.
+    let mut policy = WarningPolicy::new();
+    for (codes, action) in [
+        (&args.allow_warnings, WarningAction::Allow),
+        (&args.deny_warnings, WarningAction::Deny),
+    ] {
+        for code in codes {
+            match code.as_str() {
+                "all" => policy.all(action),
+                class if WarningPolicy::CLASSES.iter().any(|&(c, _)| c == class) => {
+                    policy.class(class, action)
+                }
+                code => policy.code(code, action),
+            }
+        }
+    }
+
+    let denied = policy.apply(warnings);
+    if denied.is_empty() {
+        return result;
+    }
+
+    match result {
+        Ok(_) => Err(denied),
+        Err(mut errors) => {
+            errors.extend(denied);
+            Err(errors)
+        }
+    }
+}
+
+/// Apply a color transformation to all pages of the document.
+fn transform_colors(document: &mut Document, transform: ColorTransform) {
+    let transform = match transform {
+        ColorTransform::Grayscale => typst::visualize::ColorTransform::Grayscale,
+        ColorTransform::Cmyk => typst::visualize::ColorTransform::Cmyk,
+    };
+
+    for page in &mut document.pages {
+        page.frame.map_colors(transform);
+    }
+}
+
 /// Export into the target format.
 fn export(
     world: &mut SystemWorld,
@@ -175,7 +295,12 @@ fn export(
         OutputFormat::Svg => {
             export_image(world, document, command, watching, ImageExportFormat::Svg)
         }
+        OutputFormat::Ppm => {
+            export_image(world, document, command, watching, ImageExportFormat::Ppm)
+        }
         OutputFormat::Pdf => export_pdf(document, command),
+        OutputFormat::Txt => export_text(document, command, TextFlavor::Plain),
+        OutputFormat::Md => export_text(document, command, TextFlavor::Markdown),
     }
 }
 
@@ -184,6 +309,10 @@ fn export_pdf(document: &Document, command: &CompileCommand) -> StrResult<()> {
     let timestamp = convert_datetime(
         command.common.creation_timestamp.unwrap_or_else(chrono::Utc::now),
     );
+    if command.split_chapters {
+        return export_pdf_chapters(document, command, timestamp);
+    }
+
     let exported_page_ranges = command.exported_page_ranges();
     let buffer = typst_pdf::pdf(document, Smart::Auto, timestamp, exported_page_ranges);
     command
@@ -193,6 +322,80 @@ fn export_pdf(document: &Document, command: &CompileCommand) -> StrResult<()> {
     Ok(())
 }
 
+/// Export to one PDF per chapter.
+fn export_pdf_chapters(
+    document: &Document,
+    command: &CompileCommand,
+    timestamp: Option<Datetime>,
+) -> StrResult<()> {
+    let Output::Path(output) = command.output() else {
+        bail!("cannot export multiple PDF files to stdout");
+    };
+
+    let template = output.to_str().unwrap_or_default();
+    if !output_template::has_indexable_template(template) {
+        bail!("cannot split PDF without a chapter number template ({{p}}, {{0p}}) in the output path");
+    }
+
+    // A new chapter starts on every page with a top-level heading. Pages
+    // before the first heading form a chapter of their own.
+    let mut starts = vec![0];
+    for elem in document.introspector.query(&HeadingElem::elem().select()) {
+        let heading = elem.to_packed::<HeadingElem>().unwrap();
+        if heading.resolve_level(StyleChain::default()).get() == 1 {
+            let page = document.introspector.page(elem.location().unwrap());
+            starts.push(page.get() - 1);
+        }
+    }
+    starts.dedup();
+
+    let paths: Vec<PathBuf> = (1..=starts.len())
+        .map(|i| output_template::format(template, i, starts.len()).into())
+        .collect();
+
+    // The files link to each other by name, so they must be in the same
+    // directory.
+    let names: Vec<String> = paths
+        .iter()
+        .map(|path| path.file_name().unwrap_or_default().to_string_lossy().into())
+        .collect();
+
+    let parts: Vec<(PageRanges, &str)> = starts
+        .iter()
+        .zip(starts.iter().skip(1).map(Some).chain([None]))
+        .zip(&names)
+        .map(|((&start, end), name)| {
+            let range =
+                NonZeroUsize::new(start + 1)..=end.and_then(|&e| NonZeroUsize::new(e));
+            (PageRanges::new(vec![range]), name.as_str())
+        })
+        .collect();
+
+    let buffers = typst_pdf::pdf_split(document, Smart::Auto, timestamp, &parts);
+    for (path, buffer) in paths.into_iter().zip(buffers) {
+        Output::Path(path)
+            .write(&buffer)
+            .map_err(|err| eco_format!("failed to write PDF file ({err})"))?;
+    }
+
+    Ok(())
+}
+
+/// Export the document's text.
+fn export_text(
+    document: &Document,
+    command: &CompileCommand,
+    flavor: TextFlavor,
+) -> StrResult<()> {
+    let exported_page_ranges = command.exported_page_ranges();
+    let text = text::extract(document, exported_page_ranges.as_ref(), flavor);
+    command
+        .output()
+        .write(text.as_bytes())
+        .map_err(|err| eco_format!("failed to write text file ({err})"))?;
+    Ok(())
+}
+
 /// Convert [`chrono::DateTime`] to [`Datetime`]
 fn convert_datetime(date_time: chrono::DateTime<chrono::Utc>) -> Option<Datetime> {
     Datetime::from_ymd_hms(
@@ -210,6 +413,7 @@ fn convert_datetime(date_time: chrono::DateTime<chrono::Utc>) -> Option<Datetime
 enum ImageExportFormat {
     Png,
     Svg,
+    Ppm,
 }
 
 /// Export to one or multiple images.
@@ -223,7 +427,8 @@ fn export_image(
     let output = command.output();
     // Determine whether we have indexable templates in output
     let can_handle_multiple = match output {
-        Output::Stdout => false,
+        // PPM images can be concatenated into a stream.
+        Output::Stdout => matches!(fmt, ImageExportFormat::Ppm),
         Output::Path(ref output) => {
             output_template::has_indexable_template(output.to_str().unwrap_or_default())
         }
@@ -252,6 +457,14 @@ fn export_image(
         bail!("cannot export multiple images {err}");
     }
 
+    // Pages streamed to stdout must be written in order.
+    if let Output::Stdout = output {
+        for (_, page) in &exported_pages {
+            export_image_page(command, &page.frame, &output, fmt)?;
+        }
+        return Ok(());
+    }
+
     let cache = world.export_cache();
 
     // The results are collected in a `Vec<()>` which does not allocate.
@@ -331,7 +544,12 @@ fn export_image_page(
 ) -> StrResult<()> {
     match fmt {
         ImageExportFormat::Png => {
-            let pixmap = typst_render::render(frame, command.ppi / 72.0, Color::WHITE);
+            let pixmap = typst_render::render_with(
+                frame,
+                command.ppi / 72.0,
+                Color::WHITE,
+                command.render_options(),
+            );
             let buf = pixmap
                 .encode_png()
                 .map_err(|err| eco_format!("failed to encode PNG file ({err})"))?;
@@ -345,6 +563,24 @@ fn export_image_page(
                 .write(svg.as_bytes())
                 .map_err(|err| eco_format!("failed to write SVG file ({err})"))?;
         }
+        ImageExportFormat::Ppm => {
+            let pixmap = typst_render::render_with(
+                frame,
+                command.ppi / 72.0,
+                Color::WHITE,
+                command.render_options(),
+            );
+            let mut buf =
+                format!("P6\n{} {}\n255\n", pixmap.width(), pixmap.height()).into_bytes();
+            // The background is opaque, so the premultiplied RGBA pixels
+            // already hold the final RGB values.
+            for pixel in pixmap.data().chunks_exact(4) {
+                buf.extend_from_slice(&pixel[..3]);
+            }
+            output
+                .write(&buf)
+                .map_err(|err| eco_format!("failed to write PPM file ({err})"))?;
+        }
     }
     Ok(())
 }
@@ -392,11 +628,59 @@ impl ExportCache {
     }
 }
 
+/// Writes the dependencies of the compilation to the paths specified by the
+/// `--deps` and `--make-deps` arguments, if they were provided.
+fn write_deps(world: &mut SystemWorld, command: &CompileCommand) -> StrResult<()> {
+    if let Some(path) = &command.deps {
+        match command.deps_format {
+            DepsFormat::Json => write_json_deps(world, command, path)?,
+            DepsFormat::Make => write_make_deps(world, command, path)?,
+        }
+    }
+
+    if let Some(path) = &command.make_deps {
+        write_make_deps(world, command, path)?;
+    }
+
+    Ok(())
+}
+
+/// Writes a JSON object listing the inputs and outputs of the compilation.
+fn write_json_deps(
+    world: &mut SystemWorld,
+    command: &CompileCommand,
+    deps_path: &Path,
+) -> StrResult<()> {
+    #[derive(Serialize)]
+    struct Deps {
+        inputs: Vec<String>,
+        outputs: Vec<String>,
+    }
+
+    let root = world.root().to_owned();
+    let inputs = world
+        .dependencies()
+        .map(|dep| dep.strip_prefix(&root).unwrap_or(&dep).to_string_lossy().into())
+        .collect();
+    let outputs = match command.output() {
+        Output::Path(path) => vec![path.to_string_lossy().into()],
+        Output::Stdout => vec![],
+    };
+
+    let json = serde_json::to_string_pretty(&Deps { inputs, outputs })
+        .map_err(|err| eco_format!("failed to serialize dependencies ({err})"))?;
+    fs::write(deps_path, json).map_err(|err| {
+        eco_format!("failed to create dependencies file due to IO error ({err})")
+    })
+}
+
 /// Writes a Makefile rule describing the relationship between the output and
-/// its dependencies to the path specified by the --make-deps argument, if it
-/// was provided.
-fn write_make_deps(world: &mut SystemWorld, command: &CompileCommand) -> StrResult<()> {
-    let Some(ref make_deps_path) = command.make_deps else { return Ok(()) };
+/// its dependencies to the given path.
+fn write_make_deps(
+    world: &mut SystemWorld,
+    command: &CompileCommand,
+    make_deps_path: &Path,
+) -> StrResult<()> {
     let Output::Path(output_path) = command.output() else {
         bail!("failed to create make dependencies file because output was stdout")
     };
@@ -490,13 +774,17 @@ pub fn print_diagnostics(
     warnings: &[SourceDiagnostic],
     diagnostic_format: DiagnosticFormat,
 ) -> Result<(), codespan_reporting::files::Error> {
+    if diagnostic_format == DiagnosticFormat::Json {
+        return print_json_diagnostics(world, errors, warnings);
+    }
+
     let mut config = term::Config { tab_width: 2, ..Default::default() };
     if diagnostic_format == DiagnosticFormat::Short {
         config.display_style = term::DisplayStyle::Short;
     }
 
     for diagnostic in warnings.iter().chain(errors) {
-        let diag = match diagnostic.severity {
+        let mut diag = match diagnostic.severity {
             Severity::Error => Diagnostic::error(),
             Severity::Warning => Diagnostic::warning(),
         }
@@ -509,6 +797,7 @@ pub fn print_diagnostics(
                 .collect(),
         )
         .with_labels(label(world, diagnostic.span).into_iter().collect());
+        diag.code = diagnostic.code.map(Into::into);
 
         term::emit(&mut terminal::out(), &config, world, &diag)?;
 
@@ -526,6 +815,95 @@ pub fn print_diagnostics(
     Ok(())
 }
 
+/// Print diagnostic messages as JSON lines.
+fn print_json_diagnostics(
+    world: &SystemWorld,
+    errors: &[SourceDiagnostic],
+    warnings: &[SourceDiagnostic],
+) -> Result<(), codespan_reporting::files::Error> {
+    let mut out = terminal::out();
+    for diagnostic in warnings.iter().chain(errors) {
+        let json = JsonDiagnostic {
+            severity: match diagnostic.severity {
+                Severity::Error => "error",
+                Severity::Warning => "warning",
+            },
+            message: &diagnostic.message,
+            code: diagnostic.code,
+            hints: &diagnostic.hints,
+            location: json_location(world, diagnostic.span),
+            trace: diagnostic
+                .trace
+                .iter()
+                .map(|point| JsonTracepoint {
+                    message: point.v.to_string(),
+                    location: json_location(world, point.span),
+                })
+                .collect(),
+        };
+
+        serde_json::to_writer(&mut out, &json).map_err(io::Error::from)?;
+        writeln!(out)?;
+    }
+
+    Ok(())
+}
+
+/// Determine the file and range of a span for JSON output.
+fn json_location(world: &SystemWorld, span: Span) -> Option<JsonLocation> {
+    use codespan_reporting::files::Files;
+
+    let id = span.id()?;
+    let range = world.range(span)?;
+    let position = |byte| -> Option<JsonPosition> {
+        let line = world.line_index(id, byte).ok()?;
+        let column = world.column_number(id, line, byte).ok()?;
+        Some(JsonPosition { line: line + 1, column: column + 1 })
+    };
+
+    Some(JsonLocation {
+        file: world.name(id).ok()?,
+        start: position(range.start)?,
+        end: position(range.end)?,
+        range,
+    })
+}
+
+/// A diagnostic in the JSON diagnostic format.
+#[derive(Serialize)]
+struct JsonDiagnostic<'a> {
+    severity: &'static str,
+    message: &'a str,
+    code: Option<&'static str>,
+    hints: &'a [EcoString],
+    location: Option<JsonLocation>,
+    trace: Vec<JsonTracepoint>,
+}
+
+/// A point in the stack trace of a JSON diagnostic.
+#[derive(Serialize)]
+struct JsonTracepoint {
+    message: String,
+    location: Option<JsonLocation>,
+}
+
+/// The location of a span in the JSON diagnostic format.
+#[derive(Serialize)]
+struct JsonLocation {
+    file: String,
+    /// The byte range in the file.
+    range: std::ops::Range<usize>,
+    start: JsonPosition,
+    end: JsonPosition,
+}
+
+/// A one-based line and column in the JSON diagnostic format.
+#[derive(Serialize)]
+struct JsonPosition {
+    line: usize,
+    column: usize,
+}
+
 /// Create a label for a span.
 fn label(world: &SystemWorld, span: Span) -> Option<Label<FileId>> {
     Some(Label::primary(span.id()?, world.range(span)?))
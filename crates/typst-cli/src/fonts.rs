@@ -1,5 +1,5 @@
 use std::fs;
-use std::path::PathBuf;
+use std::path::{Path, PathBuf};
 use std::sync::OnceLock;
 
 use fontdb::{Database, Source};
@@ -7,19 +7,31 @@ use typst::diag::StrResult;
 use typst::text::{Font, FontBook, FontInfo, FontVariant};
 use typst_timing::TimingScope;
 
-use crate::args::FontsCommand;
+use crate::args::{FontArgs, FontsCommand};
 
 /// Execute a font listing command.
 pub fn fonts(command: &FontsCommand) -> StrResult<()> {
     let mut searcher = FontSearcher::new();
-    searcher.search(&command.font_paths);
+    searcher.search(&command.font_args);
 
     for (name, infos) in searcher.book.families() {
+        let infos: Vec<_> = match &command.covering {
+            Some(text) => infos.filter(|info| info.covers(text)).collect(),
+            None => infos.collect(),
+        };
+        if infos.is_empty() {
+            continue;
+        }
+
         println!("{name}");
         if command.variants {
             for info in infos {
                 let FontVariant { style, weight, stretch } = info.variant;
-                println!("- Style: {style:?}, Weight: {weight:?}, Stretch: {stretch:?}");
+                let count = info.coverage.count();
+                println!(
+                    "- Style: {style:?}, Weight: {weight:?}, Stretch: {stretch:?}, \
+                     Codepoints: {count}"
+                );
             }
         }
     }
@@ -47,6 +59,15 @@ pub struct FontSlot {
 }
 
 impl FontSlot {
+    /// The path of the font file, if the font was loaded from disk.
+    ///
+    /// Fonts that were never needed or that are embedded in the binary have
+    /// no path.
+    pub fn loaded_path(&self) -> Option<&Path> {
+        let loaded = matches!(self.font.get(), Some(Some(_)));
+        (loaded && !self.path.as_os_str().is_empty()).then_some(&*self.path)
+    }
+
     /// Get the font for this slot.
     pub fn get(&self) -> Option<Font> {
         self.font
@@ -65,17 +86,19 @@ impl FontSearcher {
         Self { book: FontBook::new(), fonts: vec![] }
     }
 
-    /// Search everything that is available.
-    pub fn search(&mut self, font_paths: &[PathBuf]) {
+    /// Search everything that is available and not excluded by the arguments.
+    pub fn search(&mut self, args: &FontArgs) {
         let mut db = Database::new();
 
         // Font paths have highest priority.
-        for path in font_paths {
+        for path in &args.font_paths {
             db.load_fonts_dir(path);
         }
 
         // System fonts have second priority.
-        db.load_system_fonts();
+        if !args.ignore_system_fonts {
+            db.load_system_fonts();
+        }
 
         for face in db.faces() {
             let path = match &face.source {
@@ -101,7 +124,9 @@ impl FontSearcher {
 
         // Embedded fonts have lowest priority.
         #[cfg(feature = "embed-fonts")]
-        self.add_embedded();
+        if !args.ignore_embedded_fonts {
+            self.add_embedded();
+        }
     }
 
     /// Add fonts that are embedded in the binary.
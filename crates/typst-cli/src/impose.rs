@@ -0,0 +1,161 @@
+//! Arrangement of pages on printed sheets.
+
+use std::num::NonZeroUsize;
+
+use typst::layout::{Abs, Frame, Page, Point, Sides, Size};
+use typst::model::Document;
+use typst::utils::NonZeroExt;
+
+use crate::args::Imposition;
+
+/// Rearrange the pages of a document for printing.
+///
+/// This operates on the finished pages, so the result is meant for print
+/// only: Links and outline entries still refer to the original pages.
+///
+/// Crop marks are placed around the trimmed part of each page, outside of its
+/// bleed. Pages that already have marks from `page(marks: true)` are left
+/// as they are. A sheet holds multiple pages and thus has no single trim or
+/// bleed box. Each page on it keeps its marks instead.
+pub fn impose(document: &mut Document, imposition: Option<Imposition>, crop_marks: bool) {
+    if crop_marks {
+        for page in &mut document.pages {
+            page.add_crop_marks();
+        }
+    }
+
+    let Some(imposition) = imposition else { return };
+    let pages = std::mem::take(&mut document.pages);
+    let cell = pages
+        .iter()
+        .fold(Size::zero(), |size, page| size.max(page.frame.size()));
+    let frames: Vec<Option<Frame>> =
+        pages.into_iter().map(|page| Some(page.frame)).collect();
+
+    let sheets: Vec<Vec<Option<Frame>>> = match imposition {
+        Imposition::TwoUp => chunks(frames, 2),
+        Imposition::FourUp => chunks(frames, 4),
+        Imposition::Booklet => booklet(frames),
+    };
+
+    let columns = 2;
+    document.pages = sheets
+        .into_iter()
+        .enumerate()
+        .map(|(i, cells)| {
+            let rows = cells.len().div_ceil(columns);
+            let size = Size::new(cell.x * columns as f64, cell.y * rows as f64);
+            let mut frame = Frame::hard(size);
+            for (j, cell_frame) in cells.into_iter().enumerate() {
+                let Some(cell_frame) = cell_frame else { continue };
+                let (column, row) = (j % columns, j / columns);
+                let origin = Point::new(cell.x * column as f64, cell.y * row as f64);
+                // Center smaller pages in their cell.
+                let centered = ((cell - cell_frame.size()) / 2.0).to_point();
+                frame.push_frame(origin + centered, cell_frame);
+            }
+            Page {
+                frame,
+                margin: Sides::default(),
+                columns: NonZeroUsize::ONE,
+                numbering: None,
+                number: i + 1,
+                transition: None,
+                duration: None,
+                bleed: Abs::zero(),
+                slug: Abs::zero(),
+            }
+        })
+        .collect();
+}
+
+/// Split the pages into sheets of `n` consecutive pages.
+fn chunks(frames: Vec<Option<Frame>>, n: usize) -> Vec<Vec<Option<Frame>>> {
+    let mut sheets = vec![];
+    let mut frames = frames.into_iter().peekable();
+    while frames.peek().is_some() {
+        let mut sheet: Vec<_> = frames.by_ref().take(n).collect();
+        sheet.resize_with(n, || None);
+        sheets.push(sheet);
+    }
+    sheets
+}
+
+/// Reorder the pages into the sides of sheets that are folded in the middle
+/// and stacked into a booklet.
+///
+/// The page count is padded with blank pages to a multiple of four. The
+/// sheets should be printed double-sided, flipping on the short edge.
+fn booklet(mut frames: Vec<Option<Frame>>) -> Vec<Vec<Option<Frame>>> {
+    let n = frames.len().next_multiple_of(4);
+    frames.resize_with(n, || None);
+
+    let mut sides = vec![];
+    for i in 0..n / 4 {
+        let front = [n - 1 - 2 * i, 2 * i];
+        let back = [2 * i + 1, n - 2 - 2 * i];
+        for side in [front, back] {
+            sides.push(side.iter().map(|&k| frames[k].take()).collect());
+        }
+    }
+    sides
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Create a document with pages whose widths are their page numbers.
+    fn document(n: usize) -> Document {
+        let mut document = Document::default();
+        for i in 1..=n {
+            document.pages.push(Page {
+                frame: Frame::hard(Size::new(Abs::pt(i as f64), Abs::pt(10.0))),
+                margin: Sides::default(),
+                columns: NonZeroUsize::ONE,
+                numbering: None,
+                number: i,
+                transition: None,
+                duration: None,
+                bleed: Abs::zero(),
+                slug: Abs::zero(),
+            });
+        }
+        document
+    }
+
+    /// The page number of a cell, derived from its width, or zero if blank.
+    fn number(frame: Option<Frame>) -> f64 {
+        frame.map_or(0.0, |frame| frame.width().to_pt())
+    }
+
+    #[test]
+    fn test_booklet_order() {
+        let frames = document(5).pages.into_iter().map(|page| Some(page.frame));
+        let sides: Vec<Vec<f64>> = booklet(frames.collect())
+            .into_iter()
+            .map(|side| side.into_iter().map(number).collect())
+            .collect();
+        assert_eq!(
+            sides,
+            [[0.0, 1.0], [2.0, 0.0], [0.0, 3.0], [4.0, 5.0]].map(Vec::from)
+        );
+    }
+
+    #[test]
+    fn test_crop_marks_keep_trim_and_bleed() {
+        let mut document = document(1);
+        document.pages[0].frame = Frame::hard(Size::splat(Abs::pt(20.0)));
+        document.pages[0].bleed = Abs::pt(2.0);
+        impose(&mut document, None, true);
+        let page = &document.pages[0];
+        assert_eq!(page.bleed, Abs::pt(2.0));
+        assert!(!page.slug.is_zero());
+        assert_eq!(page.frame.width(), Abs::pt(20.0) + 2.0 * page.slug);
+
+        // Adding marks again doesn't enlarge the page.
+        let size = page.frame.size();
+        impose(&mut document, None, true);
+        assert_eq!(document.pages[0].frame.size(), size);
+    }
+}
@@ -2,10 +2,13 @@ mod args;
 mod compile;
 mod download;
 mod fonts;
+mod impose;
 mod init;
 mod package;
 mod query;
+mod stats;
 mod terminal;
+mod text;
 mod timings;
 #[cfg(feature = "self-update")]
 mod update;
@@ -41,6 +44,7 @@ fn main() -> ExitCode {
         Command::Watch(command) => crate::watch::watch(timer, command.clone()),
         Command::Init(command) => crate::init::init(command),
         Command::Query(command) => crate::query::query(command),
+        Command::Stats(command) => crate::stats::stats(command),
         Command::Fonts(command) => crate::fonts::fonts(command),
         Command::Update(command) => crate::update::update(command),
     };
@@ -9,7 +9,7 @@ use typst::syntax::Span;
 use typst::World;
 
 use crate::args::{QueryCommand, SerializationFormat};
-use crate::compile::print_diagnostics;
+use crate::compile::{apply_warning_policy, print_diagnostics};
 use crate::set_failed;
 use crate::world::SystemWorld;
 
@@ -22,8 +22,10 @@ pub fn query(command: &QueryCommand) -> StrResult<()> {
     world.source(world.main()).map_err(|err| err.to_string())?;
 
     let mut tracer = Tracer::new();
-    let result = typst::compile(&world, &mut tracer);
-    let warnings = tracer.warnings();
+    let result =
+        typst::compile_with(&world, &mut tracer, command.common.max_layout_iterations);
+    let mut warnings = tracer.warnings();
+    let result = apply_warning_policy(&command.common, result, &mut warnings);
 
     match result {
         // Retrieve and print query results.
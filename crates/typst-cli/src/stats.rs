@@ -0,0 +1,135 @@
+use ecow::{eco_format, EcoString};
+use serde::Serialize;
+use typst::diag::StrResult;
+use typst::eval::Tracer;
+use typst::foundations::{NativeElement, StyleChain};
+use typst::introspection::TextStats;
+use typst::model::{Document, HeadingElem};
+use typst::World;
+
+use crate::args::{SerializationFormat, StatsCommand};
+use crate::compile::{apply_warning_policy, print_diagnostics};
+use crate::set_failed;
+use crate::world::SystemWorld;
+
+/// Execute a stats command.
+pub fn stats(command: &StatsCommand) -> StrResult<()> {
+    let mut world = SystemWorld::new(&command.common)?;
+
+    // Reset everything and ensure that the main file is present.
+    world.reset();
+    world.source(world.main()).map_err(|err| err.to_string())?;
+
+    let mut tracer = Tracer::new();
+    let result =
+        typst::compile_with(&world, &mut tracer, command.common.max_layout_iterations);
+    let mut warnings = tracer.warnings();
+    let result = apply_warning_policy(&command.common, result, &mut warnings);
+
+    match result {
+        // Compute and print the statistics.
+        Ok(document) => {
+            let stats = collect(&document);
+            let output = match command.format {
+                Some(format) => serialize(&stats, format)?,
+                None => display(&stats),
+            };
+            println!("{output}");
+            print_diagnostics(&world, &[], &warnings, command.common.diagnostic_format)
+                .map_err(|err| eco_format!("failed to print diagnostics ({err})"))?;
+        }
+
+        // Print diagnostics.
+        Err(errors) => {
+            set_failed();
+            print_diagnostics(
+                &world,
+                &errors,
+                &warnings,
+                command.common.diagnostic_format,
+            )
+            .map_err(|err| eco_format!("failed to print diagnostics ({err})"))?;
+        }
+    }
+
+    Ok(())
+}
+
+/// Statistics about a whole document.
+#[derive(Serialize)]
+struct DocumentStats {
+    words: usize,
+    characters: usize,
+    pages: usize,
+    sections: Vec<SectionStats>,
+}
+
+/// Statistics about the section following a heading.
+#[derive(Serialize)]
+struct SectionStats {
+    heading: EcoString,
+    level: usize,
+    page: usize,
+    words: usize,
+    characters: usize,
+}
+
+/// Gather the statistics of a document from its introspector.
+fn collect(document: &Document) -> DocumentStats {
+    let introspector = &document.introspector;
+    let TextStats { words, chars } = introspector.stats();
+    let headings = HeadingElem::elem().select();
+    let sections = introspector
+        .query(&headings)
+        .iter()
+        .filter_map(|elem| {
+            let location = elem.location()?;
+            let heading = elem.to_packed::<HeadingElem>()?;
+            let stats = introspector.section_stats(location, &headings)?;
+            Some(SectionStats {
+                heading: heading.body().plain_text(),
+                level: heading.resolve_level(StyleChain::default()).get(),
+                page: introspector.page(location).get(),
+                words: stats.words,
+                characters: stats.chars,
+            })
+        })
+        .collect();
+
+    DocumentStats {
+        words,
+        characters: chars,
+        pages: document.pages.len(),
+        sections,
+    }
+}
+
+/// Format the statistics for humans.
+fn display(stats: &DocumentStats) -> String {
+    let mut output = format!(
+        "{} words, {} characters, {} pages",
+        stats.words, stats.characters, stats.pages
+    );
+
+    for section in &stats.sections {
+        let indent = "  ".repeat(section.level - 1);
+        output.push_str(&format!(
+            "\n{indent}{} (page {}): {} words, {} characters",
+            section.heading, section.page, section.words, section.characters,
+        ));
+    }
+
+    output
+}
+
+/// Serialize the statistics to the output format.
+fn serialize(data: &impl Serialize, format: SerializationFormat) -> StrResult<String> {
+    match format {
+        SerializationFormat::Json => {
+            serde_json::to_string_pretty(data).map_err(|e| eco_format!("{e}"))
+        }
+        SerializationFormat::Yaml => {
+            serde_yaml::to_string(&data).map_err(|e| eco_format!("{e}"))
+        }
+    }
+}
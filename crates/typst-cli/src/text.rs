@@ -0,0 +1,481 @@
+//! Extraction of a document's text as plain text or Markdown.
+
+use ecow::EcoString;
+use typst::foundations::StyleChain;
+use typst::layout::{Abs, Frame, FrameItem, PageRanges, Point, Size, Transform};
+use typst::model::{Destination, Document, HeadingElem};
+use typst::text::{FontStyle, FontWeight, TextItem};
+
+/// Markers that bulleted lists commonly use.
+const BULLETS: &[&str] = &["•", "‣", "–", "-", "∙", "◦", "▪"];
+
+/// The flavor of text to extract.
+#[derive(Debug, Copy, Clone, Eq, PartialEq)]
+pub enum TextFlavor {
+    /// Just the text, with lines and paragraphs.
+    Plain,
+    /// Markdown with headings, emphasis, strong emphasis, bulleted lists, and
+    /// links to URLs.
+    Markdown,
+}
+
+/// Extract the text on the exported pages of a document.
+///
+/// Lines and paragraphs are reconstructed from the positions of the text on
+/// the pages, so the result follows the order in which the text was laid out.
+/// Structure that isn't visible in the text itself, like headings in
+/// Markdown, is recovered from the elements that produced the text. Lists are
+/// recognized by their bullets and nested by the bullets' indentation.
+pub fn extract(
+    document: &Document,
+    ranges: Option<&PageRanges>,
+    flavor: TextFlavor,
+) -> String {
+    let mut extractor = Extractor {
+        flavor,
+        output: String::new(),
+        last: None,
+        heading: None,
+        in_heading: false,
+        emph: false,
+        strong: false,
+        list: vec![],
+        link: None,
+    };
+
+    for (i, page) in document.pages.iter().enumerate() {
+        if ranges.is_some_and(|ranges| !ranges.includes_page_index(i)) {
+            continue;
+        }
+        extractor.paragraph();
+        extractor.frame(&page.frame, Transform::identity());
+    }
+
+    extractor.close_emphasis();
+    let mut output = extractor.output.trim().to_string();
+    output.push('\n');
+    output
+}
+
+/// Walks through frames and accumulates their text.
+struct Extractor {
+    /// The flavor of text to produce.
+    flavor: TextFlavor,
+    /// The text extracted so far.
+    output: String,
+    /// The last run of text in the current paragraph.
+    last: Option<Run>,
+    /// The level of a heading whose text comes next.
+    heading: Option<usize>,
+    /// Whether the current paragraph is a heading.
+    in_heading: bool,
+    /// Whether Markdown emphasis is open.
+    emph: bool,
+    /// Whether Markdown strong emphasis is open.
+    strong: bool,
+    /// The horizontal positions of the bullets of the enclosing lists, from
+    /// the outermost to the innermost one.
+    list: Vec<Abs>,
+    /// The URL of the last Markdown link and where it ends in the output.
+    link: Option<(EcoString, usize)>,
+}
+
+/// The position and size of a run of text.
+#[derive(Copy, Clone)]
+struct Run {
+    /// The baseline of the run.
+    y: Abs,
+    /// The horizontal start of the run.
+    start: Abs,
+    /// The horizontal end of the run.
+    end: Abs,
+    /// The font size of the run.
+    size: Abs,
+    /// Where the run's text starts in the output.
+    offset: usize,
+    /// Where the run's emphasis delimiters start in the output. Differs from
+    /// `offset` if the run opened emphasis.
+    markup: usize,
+    /// Whether the run's text is already part of a link.
+    linked: bool,
+}
+
+impl Extractor {
+    /// Extract the text in a frame.
+    fn frame(&mut self, frame: &Frame, ts: Transform) {
+        for (pos, item) in frame.items() {
+            match item {
+                FrameItem::Group(group) => {
+                    let ts = ts
+                        .pre_concat(Transform::translate(pos.x, pos.y))
+                        .pre_concat(group.transform);
+                    self.frame(&group.frame, ts);
+                }
+                FrameItem::Text(text) => self.text(pos.transform(ts), text),
+                FrameItem::Link(Destination::Url(url), size)
+                    if self.flavor == TextFlavor::Markdown =>
+                {
+                    self.link(pos.transform(ts), *size, url);
+                }
+                FrameItem::Tag(elem) if self.flavor == TextFlavor::Markdown => {
+                    if let Some(heading) = elem.to_packed::<HeadingElem>() {
+                        let level = heading.resolve_level(StyleChain::default());
+                        self.heading = Some(level.get());
+                    }
+                }
+                _ => {}
+            }
+        }
+    }
+
+    /// Extract a run of text at the given position.
+    fn text(&mut self, pos: Point, text: &TextItem) {
+        let mut starts_line = true;
+        if let Some(last) = self.last {
+            let size = last.size.max(text.size);
+            let dy = pos.y - last.y;
+            if dy.abs() < 0.2 * size {
+                // Separate runs on the same line by a space if there is a gap
+                // between them.
+                if pos.x - last.end > 0.1 * size
+                    && !self.output.ends_with(char::is_whitespace)
+                {
+                    self.output.push(' ');
+                }
+                starts_line = false;
+            } else if dy > Abs::zero() && dy < 1.6 * size && self.heading.is_none() {
+                self.line_break();
+            } else {
+                self.paragraph();
+            }
+        }
+
+        if let Some(level) = self.heading.take() {
+            self.paragraph();
+            self.output.push_str(&"#".repeat(level));
+            self.output.push(' ');
+            self.in_heading = true;
+        }
+
+        if self.flavor == TextFlavor::Markdown && starts_line && !self.in_heading {
+            let bullet = BULLETS.contains(&text.text.trim());
+            self.list_item(pos.x, 0.2 * text.size, bullet);
+            if bullet {
+                self.output.push_str("- ");
+                self.last = Some(Run {
+                    y: pos.y,
+                    start: pos.x,
+                    end: pos.x + text.width(),
+                    size: text.size,
+                    offset: self.output.len(),
+                    markup: self.output.len(),
+                    linked: false,
+                });
+                return;
+            }
+        }
+
+        let offset;
+        let mut markup = self.output.len();
+        if self.flavor == TextFlavor::Markdown {
+            let variant = text.font.info().variant;
+            let emph = variant.style != FontStyle::Normal;
+            let strong = variant.weight >= FontWeight::SEMIBOLD && !self.in_heading;
+            if emph != self.emph || strong != self.strong {
+                self.close_emphasis();
+                markup = self.output.len();
+                if strong {
+                    self.output.push_str("**");
+                }
+                if emph {
+                    self.output.push('_');
+                }
+                self.emph = emph;
+                self.strong = strong;
+            }
+            offset = self.output.len();
+            for c in text.text.chars() {
+                if matches!(c, '\\' | '*' | '_' | '`' | '[' | ']' | '#' | '<') {
+                    self.output.push('\\');
+                }
+                self.output.push(c);
+            }
+        } else {
+            offset = self.output.len();
+            self.output.push_str(&text.text);
+        }
+
+        self.last = Some(Run {
+            y: pos.y,
+            start: pos.x,
+            end: pos.x + text.width(),
+            size: text.size,
+            offset,
+            markup,
+            linked: false,
+        });
+    }
+
+    /// Track the nesting of lists for a line starting at `x`, which starts a
+    /// list item if `bullet` is true.
+    fn list_item(&mut self, x: Abs, tolerance: Abs, bullet: bool) {
+        // Lines that start left of a bullet leave its list. Continuation lines
+        // of an item start right of its bullet.
+        while self.list.last().is_some_and(|&outer| {
+            if bullet {
+                outer > x + tolerance
+            } else {
+                outer >= x - tolerance
+            }
+        }) {
+            self.list.pop();
+        }
+
+        if bullet {
+            if !self.list.last().is_some_and(|&outer| (outer - x).abs() <= tolerance) {
+                self.list.push(x);
+            }
+            let depth = self.list.len() - 1;
+            self.output.push_str(&"  ".repeat(depth));
+        }
+    }
+
+    /// Turn the last run of text into a link if the link's area contains it.
+    fn link(&mut self, pos: Point, size: Size, url: &EcoString) {
+        let Some(run) = &mut self.last else { return };
+        if run.linked
+            || run.start < pos.x
+            || run.start > pos.x + size.x
+            || run.y < pos.y
+            || run.y > pos.y + size.y
+        {
+            return;
+        }
+        run.linked = true;
+
+        // Emphasis that the run opened must also be closed within the link
+        // text. Otherwise, the link is nested in the emphasis.
+        let opened = run.markup < run.offset;
+        let start = if opened { run.markup } else { run.offset };
+
+        // Merge the link with the previous one if it continues it, e.g. after a
+        // line break or a change in emphasis.
+        let suffix = format!("]({url})");
+        match &self.link {
+            Some((prev, end))
+                if prev == url && self.output[*end..start].trim().is_empty() =>
+            {
+                self.output.replace_range(*end - suffix.len()..*end, "");
+            }
+            _ => self.output.insert(start, '['),
+        }
+
+        if opened {
+            self.close_emphasis();
+        }
+
+        // Markdown doesn't allow whitespace at the end of the link text.
+        let trailing = self.output.len() - self.output.trim_end().len();
+        let whitespace = self.output.split_off(self.output.len() - trailing);
+        self.output.push_str(&suffix);
+        self.link = Some((url.clone(), self.output.len()));
+        self.output.push_str(&whitespace);
+    }
+
+    /// Start a new line within the current paragraph.
+    fn line_break(&mut self) {
+        self.close_emphasis();
+        trim_end(&mut self.output);
+        // Markdown headings must fit on a single line.
+        self.output.push(if self.in_heading { ' ' } else { '\n' });
+    }
+
+    /// Start a new paragraph.
+    fn paragraph(&mut self) {
+        self.close_emphasis();
+        trim_end(&mut self.output);
+        if !self.output.is_empty() {
+            self.output.push_str("\n\n");
+        }
+        self.last = None;
+        self.in_heading = false;
+        self.link = None;
+    }
+
+    /// Close any open Markdown emphasis.
+    fn close_emphasis(&mut self) {
+        // Markdown doesn't allow whitespace before closing delimiters.
+        let trailing = self.output.len() - self.output.trim_end().len();
+        let whitespace = self.output.split_off(self.output.len() - trailing);
+        if self.emph {
+            self.output.push('_');
+        }
+        if self.strong {
+            self.output.push_str("**");
+        }
+        self.output.push_str(&whitespace);
+        self.emph = false;
+        self.strong = false;
+    }
+}
+
+/// Remove trailing whitespace from a string in place.
+fn trim_end(string: &mut String) {
+    string.truncate(string.trim_end().len());
+}
+
+#[cfg(test)]
+mod tests {
+    use std::num::NonZeroUsize;
+
+    use typst::foundations::Bytes;
+    use typst::layout::{Em, Page, Sides};
+    use typst::syntax::Span;
+    use typst::text::{Font, FontVariant, Glyph, Lang};
+    use typst::utils::NonZeroExt;
+    use typst::visualize::Color;
+
+    use super::*;
+
+    /// A run of text whose glyphs are all half an em wide.
+    fn text(font: &Font, text: &str) -> FrameItem {
+        let glyphs = text
+            .char_indices()
+            .map(|(i, c)| Glyph {
+                id: 0,
+                x_advance: Em::new(0.5),
+                x_offset: Em::zero(),
+                range: i as u16..(i + c.len_utf8()) as u16,
+                span: (Span::detached(), 0),
+            })
+            .collect();
+        FrameItem::Text(TextItem {
+            font: font.clone(),
+            size: Abs::pt(10.0),
+            fill: Color::BLACK.into(),
+            stroke: None,
+            lang: Lang::ENGLISH,
+            region: None,
+            text: text.into(),
+            glyphs,
+        })
+    }
+
+    /// Extract the text of a single page with the given items.
+    fn extract_items(flavor: TextFlavor, items: Vec<(Point, FrameItem)>) -> String {
+        let mut frame = Frame::hard(Size::splat(Abs::pt(100.0)));
+        for (pos, item) in items {
+            frame.push(pos, item);
+        }
+        let mut document = Document::default();
+        document.pages.push(Page {
+            frame,
+            margin: Sides::default(),
+            columns: NonZeroUsize::ONE,
+            numbering: None,
+            number: 1,
+            transition: None,
+            duration: None,
+            bleed: Abs::zero(),
+            slug: Abs::zero(),
+        });
+        extract(&document, None, flavor)
+    }
+
+    fn font() -> Font {
+        font_with(FontVariant::default())
+    }
+
+    fn font_with(variant: FontVariant) -> Font {
+        typst_assets::fonts()
+            .flat_map(|data| Font::iter(Bytes::from_static(data)))
+            .find(|font| font.info().variant == variant)
+            .unwrap()
+    }
+
+    fn pt(x: f64, y: f64) -> Point {
+        Point::new(Abs::pt(x), Abs::pt(y))
+    }
+
+    #[test]
+    fn test_extract_lines_and_paragraphs() {
+        let font = font();
+        let items = vec![
+            (pt(0.0, 10.0), text(&font, "Hello")),
+            (pt(30.0, 10.0), text(&font, "world")),
+            (pt(0.0, 24.0), text(&font, "Next")),
+            (pt(0.0, 60.0), text(&font, "Apart")),
+        ];
+        assert_eq!(
+            extract_items(TextFlavor::Plain, items),
+            "Hello world\nNext\n\nApart\n"
+        );
+    }
+
+    #[test]
+    fn test_extract_markdown_list() {
+        let font = font();
+        let items = vec![
+            (pt(0.0, 10.0), text(&font, "•")),
+            (pt(10.0, 10.0), text(&font, "One")),
+            (pt(0.0, 24.0), text(&font, "•")),
+            (pt(10.0, 24.0), text(&font, "Two")),
+            (pt(10.0, 38.0), text(&font, "‣")),
+            (pt(20.0, 38.0), text(&font, "Three")),
+            (pt(0.0, 60.0), text(&font, "After")),
+        ];
+        assert_eq!(
+            extract_items(TextFlavor::Markdown, items.clone()),
+            "- One\n- Two\n  - Three\n\nAfter\n"
+        );
+        assert_eq!(
+            extract_items(TextFlavor::Plain, items),
+            "• One\n• Two\n‣ Three\n\nAfter\n"
+        );
+    }
+
+    #[test]
+    fn test_extract_markdown_link() {
+        let font = font();
+        let url = Destination::Url("https://typst.app".into());
+        let size = Size::new(Abs::pt(25.0), Abs::pt(12.0));
+        let items = vec![
+            (pt(0.0, 10.0), text(&font, "See")),
+            (pt(20.0, 10.0), text(&font, "Typst")),
+            (pt(20.0, 0.0), FrameItem::Link(url.clone(), size)),
+            (pt(50.0, 10.0), text(&font, "now")),
+            (pt(0.0, 24.0), text(&font, "Type")),
+            (pt(0.0, 14.0), FrameItem::Link(url.clone(), size)),
+            (pt(0.0, 38.0), text(&font, "set")),
+            (pt(0.0, 28.0), FrameItem::Link(url, size)),
+        ];
+        assert_eq!(
+            extract_items(TextFlavor::Markdown, items),
+            "See [Typst](https://typst.app) now\n[Type\nset](https://typst.app)\n"
+        );
+    }
+
+    #[test]
+    fn test_extract_markdown_emphasized_link() {
+        let font = font();
+        let italic =
+            font_with(FontVariant { style: FontStyle::Italic, ..font.info().variant });
+        let url = Destination::Url("https://typst.app".into());
+        let size = Size::new(Abs::pt(25.0), Abs::pt(12.0));
+        let items = vec![
+            (pt(0.0, 10.0), text(&font, "See")),
+            (pt(20.0, 10.0), text(&italic, "Typst")),
+            (pt(20.0, 0.0), FrameItem::Link(url.clone(), size)),
+            (pt(50.0, 10.0), text(&italic, "now")),
+            (pt(0.0, 24.0), text(&italic, "Just")),
+            (pt(25.0, 24.0), text(&italic, "this")),
+            (pt(25.0, 14.0), FrameItem::Link(url, size)),
+        ];
+        assert_eq!(
+            extract_items(TextFlavor::Markdown, items),
+            "See [_Typst_](https://typst.app) _now_
+_Just [this](https://typst.app)_
+"
+        );
+    }
+}
@@ -1,6 +1,7 @@
 use std::collections::HashMap;
-use std::io::Read;
+use std::io::{Read, Write};
 use std::path::{Path, PathBuf};
+use std::process::{Command, Stdio};
 use std::sync::OnceLock;
 use std::{fmt, fs, io, mem};
 
@@ -48,6 +49,11 @@ pub struct SystemWorld {
     /// The export cache, used for caching output files in `typst watch`
     /// sessions.
     export_cache: ExportCache,
+    /// External commands that convert raw blocks to SVG, by language.
+    raw_converters: HashMap<String, Vec<String>>,
+    /// The outputs of successful raw block conversions, keyed by language and
+    /// a hash of the text. Kept across compilations.
+    converted: Mutex<HashMap<(String, u128), Bytes>>,
 }
 
 impl SystemWorld {
@@ -103,7 +109,7 @@ impl SystemWorld {
         };
 
         let mut searcher = FontSearcher::new();
-        searcher.search(&command.font_paths);
+        searcher.search(&command.font_args);
 
         let now = match command.creation_timestamp {
             Some(time) => Now::Fixed(time),
@@ -120,6 +126,14 @@ impl SystemWorld {
             slots: Mutex::new(HashMap::new()),
             now,
             export_cache: ExportCache::new(),
+            raw_converters: command
+                .raw_converters
+                .iter()
+                .map(|(lang, cmd)| {
+                    (lang.clone(), cmd.split_whitespace().map(Into::into).collect())
+                })
+                .collect(),
+            converted: Mutex::new(HashMap::new()),
         })
     }
 
@@ -138,13 +152,16 @@ impl SystemWorld {
         self.workdir.as_deref().unwrap_or(Path::new("."))
     }
 
-    /// Return all paths the last compilation depended on.
+    /// Return all paths the last compilation depended on, including the files
+    /// of the fonts it used.
     pub fn dependencies(&mut self) -> impl Iterator<Item = PathBuf> + '_ {
+        let fonts = self.fonts.iter().filter_map(FontSlot::loaded_path);
         self.slots
             .get_mut()
             .values()
             .filter(|slot| slot.accessed())
             .filter_map(|slot| system_path(&self.root, slot.id).ok())
+            .chain(fonts.map(Path::to_path_buf))
     }
 
     /// Reset the compilation state in preparation of a new compilation.
@@ -215,6 +232,23 @@ impl World for SystemWorld {
             with_offset.day().try_into().ok()?,
         )
     }
+
+    fn convert_raw(&self, lang: &str, text: &str) -> Option<FileResult<Bytes>> {
+        let command = self.raw_converters.get(lang)?;
+        let key = (lang.to_string(), typst::utils::hash128(text));
+        if let Some(output) = self.converted.lock().get(&key) {
+            return Some(Ok(output.clone()));
+        }
+
+        // The lock is not held while the converter runs so that other raw
+        // blocks can be converted in the meantime. Failures are not cached, so
+        // that they are retried in the next compilation.
+        let result = run_converter(command, text, &self.root);
+        if let Ok(output) = &result {
+            self.converted.lock().insert(key, output.clone());
+        }
+        Some(result)
+    }
 }
 
 impl SystemWorld {
@@ -228,6 +262,49 @@ impl SystemWorld {
     }
 }
 
+/// Runs an external raw block converter, feeding it the text through stdin
+/// and reading the SVG from stdout.
+fn run_converter(command: &[String], text: &str, root: &Path) -> FileResult<Bytes> {
+    let _scope = TimingScope::new("convert raw", None);
+    let (program, args) = command
+        .split_first()
+        .ok_or_else(|| FileError::Other(Some("raw converter command is empty".into())))?;
+
+    let failed = |err: io::Error| {
+        FileError::Other(Some(eco_format!(
+            "failed to run raw converter {program}: {err}"
+        )))
+    };
+
+    let mut child = Command::new(program)
+        .args(args)
+        .current_dir(root)
+        .stdin(Stdio::piped())
+        .stdout(Stdio::piped())
+        .stderr(Stdio::piped())
+        .spawn()
+        .map_err(failed)?;
+
+    // Write the input on a separate thread so that the converter can't block
+    // on a full stdout pipe while we are still writing.
+    let mut stdin = child.stdin.take().unwrap();
+    let input = text.to_string();
+    let writer = std::thread::spawn(move || stdin.write_all(input.as_bytes()));
+    let output = child.wait_with_output().map_err(failed)?;
+    writer.join().unwrap().map_err(failed)?;
+
+    if !output.status.success() {
+        let stderr = String::from_utf8_lossy(&output.stderr);
+        return Err(FileError::Other(Some(eco_format!(
+            "raw converter {program} failed ({}): {}",
+            output.status,
+            stderr.trim(),
+        ))));
+    }
+
+    Ok(output.stdout.into())
+}
+
 /// Holds the processed data for a file ID.
 ///
 /// Both fields can be populated if the file is both imported and read().
@@ -355,8 +432,18 @@ fn system_path(project_root: &Path, id: FileId) -> FileResult<PathBuf> {
     }
 
     // Join the path to the root. If it tries to escape, deny
-    // access. Note: It can still escape via symlinks.
-    id.vpath().resolve(root).ok_or(FileError::AccessDenied)
+    // access.
+    let path = id.vpath().resolve(root).ok_or(FileError::AccessDenied)?;
+
+    // Also deny access if the path escapes through a symlink. Paths that
+    // can't be canonicalized don't exist, so reading them will fail anyway.
+    if let (Ok(canonical), Ok(root)) = (path.canonicalize(), root.canonicalize()) {
+        if !canonical.starts_with(root) {
+            return Err(FileError::AccessDenied);
+        }
+    }
+
+    Ok(path)
 }
 
 /// Reads a file from a `FileId`.
@@ -0,0 +1,500 @@
+//! A C API for embedding Typst into non-Rust applications.
+//!
+//! The API revolves around an opaque [`TypstWorld`] that holds the main source
+//! text, virtual files, and fonts. It is compiled with [`typst_compile_pdf`] or
+//! [`typst_compile_png`], which hand out a [`TypstBuffer`] on success and an
+//! error string on failure. A C header can be generated with `cbindgen`.
+//!
+//! Panics never unwind into the caller. They are reported as
+//! [`TYPST_INTERNAL_ERROR`] or, for functions that return a count, as zero.
+//!
+//! # Ownership
+//! - Worlds are created with [`typst_world_new`] and must be released with
+//!   [`typst_world_free`].
+//! - Buffers must be released with [`typst_buffer_free`].
+//! - Error and warning strings must be released with [`typst_string_free`].
+//!
+//! All input buffers are copied, so the caller keeps ownership of them.
+
+use std::ffi::{c_char, c_int, CStr, CString};
+use std::fmt::Write;
+use std::panic::{catch_unwind, AssertUnwindSafe};
+use std::ptr;
+
+use typst::diag::{Severity, SourceDiagnostic};
+use typst::eval::Tracer;
+use typst::foundations::{Bytes, Smart};
+use typst::layout::Frame;
+use typst::memory::MemoryWorld;
+use typst::visualize::Color;
+use typst::World;
+
+/// The operation was successful.
+pub const TYPST_OK: c_int = 0;
+/// An argument was invalid, e.g. a null pointer or non-UTF-8 text.
+pub const TYPST_INVALID_ARGUMENT: c_int = 1;
+/// The document failed to compile or export.
+pub const TYPST_COMPILE_ERROR: c_int = 2;
+/// An unexpected internal error occurred.
+pub const TYPST_INTERNAL_ERROR: c_int = 3;
+
+/// An opaque handle to a compilation environment.
+pub struct TypstWorld(MemoryWorld);
+
+/// A byte buffer owned by the library.
+#[repr(C)]
+pub struct TypstBuffer {
+    /// A pointer to the first byte.
+    pub data: *mut u8,
+    /// The number of bytes.
+    pub len: usize,
+}
+
+impl TypstBuffer {
+    /// An empty buffer that owns nothing.
+    const EMPTY: Self = Self { data: ptr::null_mut(), len: 0 };
+
+    /// Hand out ownership of bytes to the caller.
+    fn new(bytes: Vec<u8>) -> Self {
+        let boxed = bytes.into_boxed_slice();
+        let len = boxed.len();
+        Self { data: Box::into_raw(boxed).cast(), len }
+    }
+}
+
+/// Create a new world from the UTF-8 text of the main file.
+///
+/// Returns null if `text` is null or not valid UTF-8.
+///
+/// # Safety
+/// `text` must point to `len` readable bytes.
+#[no_mangle]
+pub unsafe extern "C" fn typst_world_new(
+    text: *const c_char,
+    len: usize,
+) -> *mut TypstWorld {
+    let Some(bytes) = slice(text.cast(), len) else { return ptr::null_mut() };
+    let Ok(text) = std::str::from_utf8(bytes) else { return ptr::null_mut() };
+    Box::into_raw(Box::new(TypstWorld(MemoryWorld::new(text))))
+}
+
+/// Release a world created with [`typst_world_new`].
+///
+/// # Safety
+/// `world` must be null or a pointer returned by [`typst_world_new`] that was
+/// not yet freed.
+#[no_mangle]
+pub unsafe extern "C" fn typst_world_free(world: *mut TypstWorld) {
+    if !world.is_null() {
+        drop(Box::from_raw(world));
+    }
+}
+
+/// Register all fonts in a font file or collection.
+///
+/// Returns the number of fonts that were found in the data, which is zero if
+/// an argument is null or the data is not a font.
+///
+/// # Safety
+/// `world` must be a valid world and `data` must point to `len` readable
+/// bytes.
+#[no_mangle]
+pub unsafe extern "C" fn typst_world_add_font(
+    world: *mut TypstWorld,
+    data: *const u8,
+    len: usize,
+) -> usize {
+    let (Some(world), Some(data)) = (world.as_mut(), slice(data, len)) else {
+        return 0;
+    };
+    catch_unwind(AssertUnwindSafe(|| world.0.add_fonts(Bytes::from(data)))).unwrap_or(0)
+}
+
+/// Register a virtual file at the given path.
+///
+/// Files ending in `.typ` are registered as source files and must be valid
+/// UTF-8. Paths are relative to the project root, where the main file lives.
+///
+/// # Safety
+/// `world` must be a valid world, `path` must be a null-terminated string, and
+/// `data` must point to `len` readable bytes.
+#[no_mangle]
+pub unsafe extern "C" fn typst_world_add_file(
+    world: *mut TypstWorld,
+    path: *const c_char,
+    data: *const u8,
+    len: usize,
+) -> c_int {
+    let (Some(world), Some(path), Some(data)) =
+        (world.as_mut(), c_str(path), slice(data, len))
+    else {
+        return TYPST_INVALID_ARGUMENT;
+    };
+
+    catch_unwind(AssertUnwindSafe(|| {
+        if path.ends_with(".typ") {
+            let Ok(text) = std::str::from_utf8(data) else {
+                return TYPST_INVALID_ARGUMENT;
+            };
+            world.0.add_source(path, text);
+        } else if world.0.add_file(path, Bytes::from(data)).is_err() {
+            return TYPST_INVALID_ARGUMENT;
+        }
+        TYPST_OK
+    }))
+    .unwrap_or(TYPST_INTERNAL_ERROR)
+}
+
+/// Compile the world's main file into a PDF.
+///
+/// On success, writes the PDF into `out` and returns [`TYPST_OK`]. On failure,
+/// writes a description of the errors into `error` (if it is not null) and
+/// returns an error code. In both cases, a description of the compiler's
+/// warnings is written into `warnings` (if it is not null). It is set to null
+/// if there were no warnings.
+///
+/// # Safety
+/// `world` must be a valid world, `out` must be writable, and `error` and
+/// `warnings` must be null or writable.
+#[no_mangle]
+pub unsafe extern "C" fn typst_compile_pdf(
+    world: *const TypstWorld,
+    out: *mut TypstBuffer,
+    error: *mut *mut c_char,
+    warnings: *mut *mut c_char,
+) -> c_int {
+    run(world, out, error, warnings, |world, warnings| {
+        let document = compile(world, warnings)?;
+        for page in &document.pages {
+            check_images(world, &page.frame)?;
+        }
+        Ok(typst_pdf::pdf(&document, Smart::Auto, None, None))
+    })
+}
+
+/// Compile the world's main file and render one of its pages into a PNG.
+///
+/// The page index is zero-based and the resolution is given in pixels per
+/// inch. Reports errors and warnings in the same way as
+/// [`typst_compile_pdf`].
+///
+/// # Safety
+/// `world` must be a valid world, `out` must be writable, and `error` and
+/// `warnings` must be null or writable.
+#[no_mangle]
+pub unsafe extern "C" fn typst_compile_png(
+    world: *const TypstWorld,
+    page: usize,
+    ppi: f32,
+    out: *mut TypstBuffer,
+    error: *mut *mut c_char,
+    warnings: *mut *mut c_char,
+) -> c_int {
+    run(world, out, error, warnings, |world, warnings| {
+        if !(ppi.is_finite() && ppi > 0.0) {
+            return Err((TYPST_INVALID_ARGUMENT, "resolution must be positive".into()));
+        }
+
+        let document = compile(world, warnings)?;
+        let Some(page) = document.pages.get(page) else {
+            let message = format!(
+                "page {} does not exist (document has {} pages)",
+                page + 1,
+                document.pages.len(),
+            );
+            return Err((TYPST_INVALID_ARGUMENT, message));
+        };
+
+        check_images(world, &page.frame)?;
+        let pixmap = typst_render::render(&page.frame, ppi / 72.0, Color::WHITE);
+        pixmap
+            .encode_png()
+            .map_err(|err| (TYPST_COMPILE_ERROR, format!("failed to encode PNG ({err})")))
+    })
+}
+
+/// Release a buffer handed out by the library.
+///
+/// # Safety
+/// `buffer` must have been produced by this library and not yet freed.
+#[no_mangle]
+pub unsafe extern "C" fn typst_buffer_free(buffer: TypstBuffer) {
+    if !buffer.data.is_null() {
+        drop(Box::from_raw(ptr::slice_from_raw_parts_mut(buffer.data, buffer.len)));
+    }
+}
+
+/// Release an error or warning string handed out by the library.
+///
+/// # Safety
+/// `string` must be null or have been produced by this library and not yet
+/// freed.
+#[no_mangle]
+pub unsafe extern "C" fn typst_string_free(string: *mut c_char) {
+    if !string.is_null() {
+        drop(CString::from_raw(string));
+    }
+}
+
+/// Run an export, catching panics and writing the result, error, and warnings
+/// out.
+unsafe fn run(
+    world: *const TypstWorld,
+    out: *mut TypstBuffer,
+    error: *mut *mut c_char,
+    warnings: *mut *mut c_char,
+    f: impl FnOnce(
+        &MemoryWorld,
+        &mut Vec<SourceDiagnostic>,
+    ) -> Result<Vec<u8>, (c_int, String)>,
+) -> c_int {
+    if !error.is_null() {
+        *error = ptr::null_mut();
+    }
+    if !warnings.is_null() {
+        *warnings = ptr::null_mut();
+    }
+
+    let Some(world) = world.as_ref() else { return TYPST_INVALID_ARGUMENT };
+    if out.is_null() {
+        return TYPST_INVALID_ARGUMENT;
+    }
+    *out = TypstBuffer::EMPTY;
+
+    let mut diagnostics = vec![];
+    let result = catch_unwind(AssertUnwindSafe(|| f(&world.0, &mut diagnostics)))
+        .unwrap_or_else(|_| Err((TYPST_INTERNAL_ERROR, "the compiler panicked".into())));
+
+    if !warnings.is_null() && !diagnostics.is_empty() {
+        *warnings = c_string(format_diagnostics(&world.0, &diagnostics));
+    }
+
+    match result {
+        Ok(bytes) => {
+            *out = TypstBuffer::new(bytes);
+            TYPST_OK
+        }
+        Err((code, message)) => {
+            if !error.is_null() {
+                *error = c_string(message);
+            }
+            code
+        }
+    }
+}
+
+/// Compile the world into a document, formatting the errors on failure and
+/// collecting the warnings.
+fn compile(
+    world: &MemoryWorld,
+    warnings: &mut Vec<SourceDiagnostic>,
+) -> Result<typst::model::Document, (c_int, String)> {
+    let mut tracer = Tracer::new();
+    let result = typst::compile(world, &mut tracer);
+    warnings.extend(tracer.warnings());
+    result.map_err(|errors| (TYPST_COMPILE_ERROR, format_diagnostics(world, &errors)))
+}
+
+/// Fail if the pixels of a raster image in the frame are corrupt.
+fn check_images(world: &MemoryWorld, frame: &Frame) -> Result<(), (c_int, String)> {
+    frame
+        .check_images()
+        .map_err(|errors| (TYPST_COMPILE_ERROR, format_diagnostics(world, &errors)))
+}
+
+/// Format diagnostics as `path:line:column: severity: message` lines.
+fn format_diagnostics(world: &MemoryWorld, diagnostics: &[SourceDiagnostic]) -> String {
+    let mut output = String::new();
+    for diagnostic in diagnostics {
+        if let Some(id) = diagnostic.span.id() {
+            let path = id.vpath().as_rootless_path().display();
+            let position = world.source(id).ok().and_then(|source| {
+                let start = source.range(diagnostic.span)?.start;
+                Some((source.byte_to_line(start)?, source.byte_to_column(start)?))
+            });
+            match position {
+                Some((line, column)) => {
+                    write!(output, "{path}:{}:{}: ", line + 1, column + 1).unwrap()
+                }
+                None => write!(output, "{path}: ").unwrap(),
+            }
+        }
+
+        let severity = match diagnostic.severity {
+            Severity::Error => "error",
+            Severity::Warning => "warning",
+        };
+        writeln!(output, "{severity}: {}", diagnostic.message).unwrap();
+        for hint in &diagnostic.hints {
+            writeln!(output, "  hint: {hint}").unwrap();
+        }
+    }
+    output
+}
+
+/// Hand out ownership of a string to the caller.
+fn c_string(string: String) -> *mut c_char {
+    // Interior null bytes would truncate the string, so drop them.
+    CString::new(string.replace('\0', "")).unwrap().into_raw()
+}
+
+/// Turn a pointer and a length into a slice, rejecting null pointers.
+unsafe fn slice<'a>(data: *const u8, len: usize) -> Option<&'a [u8]> {
+    match (data.is_null(), len) {
+        (_, 0) => Some(&[]),
+        (true, _) => None,
+        (false, _) => Some(std::slice::from_raw_parts(data, len)),
+    }
+}
+
+/// Turn a null-terminated UTF-8 string into a string slice.
+unsafe fn c_str<'a>(string: *const c_char) -> Option<&'a str> {
+    if string.is_null() {
+        return None;
+    }
+    CStr::from_ptr(string).to_str().ok()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Create a world from the main file's text.
+    fn world(text: &str) -> *mut TypstWorld {
+        let world = unsafe { typst_world_new(text.as_ptr().cast(), text.len()) };
+        assert!(!world.is_null());
+        world
+    }
+
+    /// Take ownership of a string handed out by the library.
+    unsafe fn take(string: *mut c_char) -> String {
+        assert!(!string.is_null());
+        let owned = CStr::from_ptr(string).to_str().unwrap().to_owned();
+        typst_string_free(string);
+        owned
+    }
+
+    #[test]
+    fn test_compile_error() {
+        let world = world("#let x = (");
+        unsafe {
+            let mut out = TypstBuffer::EMPTY;
+            let mut error = ptr::null_mut();
+            let mut warnings = ptr::null_mut();
+            let code = typst_compile_pdf(world, &mut out, &mut error, &mut warnings);
+            assert_eq!(code, TYPST_COMPILE_ERROR);
+            assert!(out.data.is_null());
+            assert!(warnings.is_null());
+            assert!(take(error).starts_with("main.typ:1:"));
+            typst_world_free(world);
+        }
+    }
+
+    #[test]
+    fn test_compile_warnings() {
+        let world = world("Hello **");
+        unsafe {
+            let mut out = TypstBuffer::EMPTY;
+            let mut error = ptr::null_mut();
+            let mut warnings = ptr::null_mut();
+            let code = typst_compile_pdf(world, &mut out, &mut error, &mut warnings);
+            assert_eq!(code, TYPST_OK);
+            assert!(error.is_null());
+            assert!(std::slice::from_raw_parts(out.data, out.len).starts_with(b"%PDF"));
+            assert!(
+                take(warnings).starts_with("main.typ:1:7: warning: no text within stars")
+            );
+            typst_buffer_free(out);
+            typst_world_free(world);
+        }
+    }
+
+    #[test]
+    fn test_invalid_arguments() {
+        let world = world("Hello");
+        let invalid = [0xff, 0xfe];
+        let path = CString::new("other.typ").unwrap();
+        unsafe {
+            assert!(typst_world_new(invalid.as_ptr().cast(), invalid.len()).is_null());
+            assert_eq!(typst_world_add_font(world, invalid.as_ptr(), invalid.len()), 0);
+            assert_eq!(
+                typst_world_add_file(
+                    world,
+                    path.as_ptr(),
+                    invalid.as_ptr(),
+                    invalid.len()
+                ),
+                TYPST_INVALID_ARGUMENT,
+            );
+
+            let mut out = TypstBuffer::EMPTY;
+            let mut error = ptr::null_mut();
+            let code =
+                typst_compile_png(world, 0, 0.0, &mut out, &mut error, ptr::null_mut());
+            assert_eq!(code, TYPST_INVALID_ARGUMENT);
+            assert_eq!(take(error), "resolution must be positive");
+
+            let code =
+                typst_compile_png(world, 1, 72.0, &mut out, &mut error, ptr::null_mut());
+            assert_eq!(code, TYPST_INVALID_ARGUMENT);
+            assert_eq!(take(error), "page 2 does not exist (document has 1 pages)");
+            assert!(out.data.is_null());
+            typst_world_free(world);
+        }
+    }
+
+    #[test]
+    fn test_null_inputs() {
+        let world = world("Hello");
+        let data = b"Hello";
+        let path = CString::new("other.typ").unwrap();
+        let (no_error, no_warnings) = (ptr::null_mut(), ptr::null_mut());
+        unsafe {
+            assert!(typst_world_new(ptr::null(), 1).is_null());
+            assert_eq!(
+                typst_world_add_font(ptr::null_mut(), data.as_ptr(), data.len()),
+                0
+            );
+            assert_eq!(typst_world_add_font(world, ptr::null(), 1), 0);
+            assert_eq!(
+                typst_world_add_file(
+                    ptr::null_mut(),
+                    path.as_ptr(),
+                    data.as_ptr(),
+                    data.len()
+                ),
+                TYPST_INVALID_ARGUMENT,
+            );
+            assert_eq!(
+                typst_world_add_file(world, ptr::null(), data.as_ptr(), data.len()),
+                TYPST_INVALID_ARGUMENT,
+            );
+            assert_eq!(
+                typst_world_add_file(world, path.as_ptr(), ptr::null(), 1),
+                TYPST_INVALID_ARGUMENT,
+            );
+
+            let mut out = TypstBuffer::EMPTY;
+            assert_eq!(
+                typst_compile_pdf(ptr::null_mut(), &mut out, no_error, no_warnings),
+                TYPST_INVALID_ARGUMENT
+            );
+            assert_eq!(
+                typst_compile_pdf(world, ptr::null_mut(), no_error, no_warnings),
+                TYPST_INVALID_ARGUMENT,
+            );
+
+            // Errors and warnings are optional.
+            assert_eq!(
+                typst_compile_pdf(world, &mut out, no_error, no_warnings),
+                TYPST_OK
+            );
+            typst_buffer_free(out);
+
+            typst_buffer_free(TypstBuffer::EMPTY);
+            typst_string_free(ptr::null_mut());
+            typst_world_free(ptr::null_mut());
+            typst_world_free(world);
+        }
+    }
+}
@@ -468,7 +468,9 @@ fn rewrite_fn_item(item: &syn::ItemFn) -> syn::ItemFn {
         Some(input)
     });
     let mut item = item.clone();
-    item.attrs.clear();
+    // Keep attributes like lint levels, but drop the docs since they are
+    // re-added in processed form.
+    item.attrs.retain(|attr| !attr.path().is_ident("doc"));
     item.sig.inputs = parse_quote! { #(#inputs),* };
     item
 }
@@ -0,0 +1,90 @@
+use std::collections::HashSet;
+use std::path::Path;
+
+use ecow::{eco_format, EcoString};
+use pdf_writer::{Finish, Name, Str, TextStr};
+use typst::foundations::{NativeElement, Packed, StyleChain};
+use typst::model::pdf::{EmbedElem, EmbeddedFileRelationship};
+use typst::utils::hash128;
+
+use crate::PdfContext;
+
+/// Write all embedded files into the PDF and remember their file
+/// specifications for the catalog's name tree.
+pub(crate) fn write_embedded_files(ctx: &mut PdfContext) {
+    let mut written = HashSet::new();
+    let elements = ctx.document.introspector.query(&EmbedElem::elem().select());
+    for elem in &elements {
+        let Some(embed) = elem.to_packed::<EmbedElem>() else { continue };
+        let data = embed.data();
+
+        // The same file embedded multiple times is only written once.
+        let base = file_name(embed);
+        if !written.insert((base.clone(), hash128(data))) {
+            continue;
+        }
+
+        // Entries in the name tree must be unique, so different files with the
+        // same name are disambiguated.
+        let name = unique_name(ctx, base);
+
+        let spec_ref = ctx.alloc.bump();
+        let file_ref = ctx.alloc.bump();
+        let styles = StyleChain::default();
+
+        let mut embedded_file = ctx.pdf.embedded_file(file_ref, data.as_slice());
+        if let Some(mime_type) = embed.mime_type(styles) {
+            embedded_file.subtype(Name(mime_type.as_bytes()));
+        }
+        embedded_file.params().size(data.len() as i32);
+        embedded_file.finish();
+
+        let mut file_spec = ctx.pdf.file_spec(spec_ref);
+        file_spec.path(Str(name.as_bytes()));
+        file_spec.unic_file(TextStr(&name));
+        file_spec.embedded_file(file_ref);
+        if let Some(description) = embed.description(styles) {
+            file_spec.description(TextStr(&description));
+        }
+        let relationship = match embed.relationship(styles) {
+            Some(EmbeddedFileRelationship::Source) => Name(b"Source"),
+            Some(EmbeddedFileRelationship::Data) => Name(b"Data"),
+            Some(EmbeddedFileRelationship::Alternative) => Name(b"Alternative"),
+            Some(EmbeddedFileRelationship::Supplement) => Name(b"Supplement"),
+            None => Name(b"Unspecified"),
+        };
+        file_spec.pair(Name(b"AFRelationship"), relationship);
+        file_spec.finish();
+
+        ctx.embedded_files.insert(name, spec_ref);
+    }
+}
+
+/// The name under which the file is listed in the PDF, which is the last
+/// component of its path.
+fn file_name(embed: &Packed<EmbedElem>) -> EcoString {
+    let path = embed.path();
+    Path::new(path.as_str())
+        .file_name()
+        .and_then(|name| name.to_str())
+        .map(Into::into)
+        .unwrap_or_else(|| path.clone())
+}
+
+/// Appends a numeric suffix to the file's stem until the name is not yet taken,
+/// e.g. `data.csv` becomes `data-2.csv`.
+fn unique_name(ctx: &PdfContext, name: EcoString) -> EcoString {
+    if !ctx.embedded_files.contains_key(&name) {
+        return name;
+    }
+
+    let (stem, ext) = match name.rsplit_once('.') {
+        Some((stem, ext)) if !stem.is_empty() => (stem, eco_format!(".{ext}")),
+        _ => (name.as_str(), EcoString::new()),
+    };
+
+    (2..)
+        .map(|i| eco_format!("{stem}-{i}{ext}"))
+        .find(|candidate| !ctx.embedded_files.contains_key(candidate))
+        .unwrap()
+}
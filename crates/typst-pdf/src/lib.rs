@@ -1,6 +1,7 @@
 //! Exporting of Typst documents into PDFs.
 
 mod color;
+mod embed;
 mod extg;
 mod font;
 mod gradient;
@@ -22,7 +23,7 @@ use pdf_writer::{Finish, Name, Pdf, Rect, Ref, Str, TextStr};
 use typst::foundations::{Datetime, Label, NativeElement, Smart};
 use typst::introspection::Location;
 use typst::layout::{Abs, Dir, Em, Frame, PageRanges, Transform};
-use typst::model::{Document, HeadingElem};
+use typst::model::{Document, Duplex, HeadingElem};
 use typst::text::color::frame_for_glyph;
 use typst::text::{Font, Lang};
 use typst::utils::Deferred;
@@ -65,13 +66,61 @@ pub fn pdf(
     timestamp: Option<Datetime>,
     page_ranges: Option<PageRanges>,
 ) -> Vec<u8> {
-    let mut ctx = PdfContext::new(document, page_ranges);
+    export(PdfContext::new(document, page_ranges), ident, timestamp)
+}
+
+/// Export a document into multiple PDF files, e.g. one per chapter.
+///
+/// Each part consists of the pages in its ranges and the file name under which
+/// it will be saved. Returns the raw bytes of each part's PDF file.
+///
+/// The parts share the page labels and outline entries of the full document.
+/// Links to pages in another part point into that part's file, which is found
+/// relative to the linking file by viewers that support this.
+///
+/// See [`pdf`] for the meaning of the `ident` and `timestamp` parameters.
+#[typst_macros::time(name = "pdf split")]
+pub fn pdf_split(
+    document: &Document,
+    ident: Smart<&str>,
+    timestamp: Option<Datetime>,
+    parts: &[(PageRanges, &str)],
+) -> Vec<Vec<u8>> {
+    // Find the file and the index within it for each page.
+    let mut counts = vec![0; parts.len()];
+    let remote: Vec<_> = (0..document.pages.len())
+        .map(|i| {
+            let part =
+                parts.iter().position(|(ranges, _)| ranges.includes_page_index(i))?;
+            counts[part] += 1;
+            Some((parts[part].1, counts[part] - 1))
+        })
+        .collect();
+
+    parts
+        .iter()
+        .map(|(ranges, _)| {
+            let mut ctx = PdfContext::new(document, Some(ranges.clone()));
+            ctx.remote.clone_from(&remote);
+            export(ctx, ident, timestamp)
+        })
+        .collect()
+}
+
+/// Write all parts of the PDF file.
+fn export(
+    mut ctx: PdfContext,
+    ident: Smart<&str>,
+    timestamp: Option<Datetime>,
+) -> Vec<u8> {
+    let document = ctx.document;
     page::construct_pages(&mut ctx, &document.pages);
     font::write_fonts(&mut ctx);
     image::write_images(&mut ctx);
     gradient::write_gradients(&mut ctx);
     extg::write_external_graphics_states(&mut ctx);
     pattern::write_patterns(&mut ctx);
+    embed::write_embedded_files(&mut ctx);
     write_named_destinations(&mut ctx);
     page::write_page_tree(&mut ctx);
     page::write_global_resources(&mut ctx);
@@ -147,6 +196,11 @@ struct PdfContext<'a> {
     dests: Vec<(Label, Ref)>,
     /// Maps from locations to named destinations that point to them.
     loc_to_dest: HashMap<Location, Label>,
+    /// For a document that is split into multiple files, the file and the
+    /// page index within it of each page of the document.
+    remote: Vec<Option<(&'a str, usize)>>,
+    /// The file specifications of embedded files, sorted by name.
+    embedded_files: BTreeMap<EcoString, Ref>,
 }
 
 impl<'a> PdfContext<'a> {
@@ -181,6 +235,8 @@ impl<'a> PdfContext<'a> {
             color_font_map: ColorFontMap::new(),
             dests: vec![],
             loc_to_dest: HashMap::new(),
+            remote: vec![],
+            embedded_files: BTreeMap::new(),
         }
     }
 }
@@ -298,7 +354,26 @@ fn write_catalog(ctx: &mut PdfContext, ident: Smart<&str>, timestamp: Option<Dat
     // Write the document catalog.
     let mut catalog = ctx.pdf.catalog(ctx.alloc.bump());
     catalog.pages(ctx.page_tree_ref);
-    catalog.viewer_preferences().direction(dir);
+
+    let mut prefs = catalog.viewer_preferences();
+    prefs.direction(dir);
+    if let Some(duplex) = ctx.document.duplex {
+        let name: &[u8] = match duplex {
+            Duplex::Simplex => b"Simplex",
+            Duplex::FlipShortEdge => b"DuplexFlipShortEdge",
+            Duplex::FlipLongEdge => b"DuplexFlipLongEdge",
+        };
+        prefs.pair(Name(b"Duplex"), Name(name));
+    }
+    if let Smart::Custom(scaling) = ctx.document.print_scaling {
+        let name: &[u8] = if scaling { b"AppDefault" } else { b"None" };
+        prefs.pair(Name(b"PrintScaling"), Name(name));
+    }
+    if let Some(copies) = ctx.document.copies {
+        prefs.pair(Name(b"NumCopies"), copies.get() as i32);
+    }
+    prefs.finish();
+
     catalog.metadata(meta_ref);
 
     // Write the named destination tree.
@@ -310,8 +385,25 @@ fn write_catalog(ctx: &mut PdfContext, ident: Smart<&str>, timestamp: Option<Dat
     }
     names.finish();
     dests_name_tree.finish();
+
+    // Write the embedded files tree.
+    if !ctx.embedded_files.is_empty() {
+        let mut embedded_files = name_dict.embedded_files();
+        let mut names = embedded_files.names();
+        for (name, spec_ref) in &ctx.embedded_files {
+            names.insert(Str(name.as_bytes()), *spec_ref);
+        }
+    }
     name_dict.finish();
 
+    // List the embedded files as associated files of the document.
+    if !ctx.embedded_files.is_empty() {
+        catalog
+            .insert(Name(b"AF"))
+            .array()
+            .items(ctx.embedded_files.values().copied());
+    }
+
     // Insert the page labels.
     if !page_labels.is_empty() {
         let mut num_tree = catalog.page_labels();
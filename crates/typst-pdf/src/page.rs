@@ -8,12 +8,13 @@ use crate::{deflate_deferred, AbsExt, EmExt, PdfContext};
 use ecow::{eco_format, EcoString};
 use pdf_writer::types::{
     ActionType, AnnotationFlags, AnnotationType, ColorSpaceOperand, LineCapStyle,
-    LineJoinStyle, NumberingStyle, TextRenderingMode,
+    LineJoinStyle, NumberingStyle, TextRenderingMode, TransitionStyle,
 };
 use pdf_writer::writers::{PageLabel, Resources};
-use pdf_writer::{Content, Filter, Finish, Name, Rect, Ref, Str, TextStr};
+use pdf_writer::{Content, Filter, Finish, Name, Null, Rect, Ref, Str, TextStr};
+use typst::foundations::Duration;
 use typst::layout::{
-    Abs, Em, Frame, FrameItem, GroupItem, Page, Point, Ratio, Size, Transform,
+    Abs, Em, Frame, FrameItem, GroupItem, Page, Point, Ratio, Size, Transform, Transition,
 };
 use typst::model::{Destination, Numbering};
 use typst::text::color::is_color_glyph;
@@ -52,6 +53,10 @@ pub(crate) fn construct_pages(ctx: &mut PdfContext, pages: &[Page]) {
                     // the corresponding real page number in the Typst document.
                     (skipped_pages > 0).then(|| PdfPageLabel::arabic(i + 1))
                 });
+            encoded.transition = page.transition;
+            encoded.duration = page.duration;
+            encoded.bleed = page.bleed;
+            encoded.slug = page.slug;
             ctx.pages.push(Some(encoded));
         }
     }
@@ -86,6 +91,10 @@ pub(crate) fn construct_page(ctx: &mut PdfContext, frame: &Frame) -> EncodedPage
         uses_opacities: ctx.uses_opacities,
         links: ctx.links,
         label: None,
+        transition: None,
+        duration: None,
+        bleed: Abs::zero(),
+        slug: Abs::zero(),
         resources: ctx.resources,
     }
 }
@@ -203,6 +212,16 @@ fn write_page(ctx: &mut PdfContext, i: usize, refs: &mut Vec<Ref>) {
     let w = page.size.x.to_f32();
     let h = page.size.y.to_f32();
     page_writer.media_box(Rect::new(0.0, 0.0, w, h));
+
+    // Mark which part of the page is bleed and which is trimmed off.
+    if !page.bleed.is_zero() || !page.slug.is_zero() {
+        let inset = |d: Abs| {
+            let d = d.to_f32();
+            Rect::new(d, d, w - d, h - d)
+        };
+        page_writer.bleed_box(inset(page.slug));
+        page_writer.trim_box(inset(page.slug + page.bleed));
+    }
     page_writer.contents(content_id);
     page_writer.pair(Name(b"Resources"), ctx.global_resources_ref);
 
@@ -216,6 +235,27 @@ fn write_page(ctx: &mut PdfContext, i: usize, refs: &mut Vec<Ref>) {
             .srgb();
     }
 
+    if let Some(transition) = page.transition {
+        let style = match transition {
+            Transition::Split => TransitionStyle::Split,
+            Transition::Blinds => TransitionStyle::Blinds,
+            Transition::Box => TransitionStyle::Box,
+            Transition::Wipe => TransitionStyle::Wipe,
+            Transition::Dissolve => TransitionStyle::Dissolve,
+            Transition::Glitter => TransitionStyle::Glitter,
+            Transition::Fly => TransitionStyle::Fly,
+            Transition::Push => TransitionStyle::Push,
+            Transition::Cover => TransitionStyle::Cover,
+            Transition::Uncover => TransitionStyle::Uncover,
+            Transition::Fade => TransitionStyle::Fade,
+        };
+        page_writer.transition().style(style);
+    }
+
+    if let Some(duration) = page.duration {
+        page_writer.duration(duration.seconds() as f32);
+    }
+
     let mut annotations = page_writer.annotations();
     for (dest, rect) in &page.links {
         let mut annotation = annotations.push();
@@ -248,7 +288,8 @@ fn write_page(ctx: &mut PdfContext, i: usize, refs: &mut Vec<Ref>) {
         let index = pos.page.get() - 1;
         let y = (pos.point.y - Abs::pt(10.0)).max(Abs::zero());
 
-        // Don't add links to non-exported pages.
+        // Don't add links to non-exported pages, unless they were exported
+        // to another file.
         if let Some(Some(page)) = ctx.pages.get(index) {
             annotation
                 .action()
@@ -256,6 +297,20 @@ fn write_page(ctx: &mut PdfContext, i: usize, refs: &mut Vec<Ref>) {
                 .destination()
                 .page(page.id)
                 .xyz(pos.point.x.to_f32(), (page.size.y - y).to_f32(), None);
+        } else if let Some(&Some((file, remote_index))) = ctx.remote.get(index) {
+            // Remote destinations refer to pages by index instead of by
+            // reference.
+            let height = ctx.document.pages[index].frame.height();
+            let mut action = annotation.action();
+            action
+                .action_type(ActionType::RemoteGoTo)
+                .pair(Name(b"F"), Str(file.as_bytes()));
+            let mut dest = action.insert(Name(b"D")).array();
+            dest.item(remote_index as i32);
+            dest.item(Name(b"XYZ"));
+            dest.item(pos.point.x.to_f32());
+            dest.item((height - y).to_f32());
+            dest.item(Null);
         }
     }
 
@@ -429,6 +484,14 @@ pub struct EncodedPage {
     pub resources: HashMap<PageResource, usize>,
     /// The page's PDF label.
     label: Option<PdfPageLabel>,
+    /// The transition effect for presentations.
+    transition: Option<Transition>,
+    /// How long the page is shown in presentations.
+    duration: Option<Duration>,
+    /// The bleed around the trimmed page.
+    bleed: Abs,
+    /// The area for printer's marks around the bleed.
+    slug: Abs,
 }
 
 /// Represents a resource being used in a PDF page by its name.
@@ -10,7 +10,7 @@ use crate::{AbsExt, State};
 
 /// Render a raster or SVG image into the canvas.
 pub fn render_image(
-    canvas: &mut sk::Pixmap,
+    canvas: &mut sk::PixmapMut,
     state: State,
     image: &Image,
     size: Size,
@@ -61,10 +61,18 @@ fn scaled_texture(image: &Image, w: u32, h: u32) -> Option<Arc<sk::Pixmap>> {
     let mut pixmap = sk::Pixmap::new(w, h)?;
     match image.kind() {
         ImageKind::Raster(raster) => {
-            let downscale = w < raster.width();
-            let filter =
-                if downscale { FilterType::Lanczos3 } else { FilterType::CatmullRom };
-            let buf = raster.dynamic().resize(w, h, filter);
+            let image = raster.srgb();
+            let buf = if w * 2 < raster.width() || h * 2 < raster.height() {
+                // When drastically downscaling, average over the area each
+                // target pixel covers. This avoids aliasing and is much faster
+                // than a convolution filter with a large footprint.
+                image.thumbnail_exact(w, h)
+            } else {
+                let downscale = w < raster.width();
+                let filter =
+                    if downscale { FilterType::Lanczos3 } else { FilterType::CatmullRom };
+                image.resize(w, h, filter)
+            };
             for ((_, _, src), dest) in buf.pixels().zip(pixmap.pixels_mut()) {
                 let Rgba([r, g, b, a]) = src;
                 *dest = sk::ColorU8::from_rgba(r, g, b, a).premultiply();
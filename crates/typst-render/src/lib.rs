@@ -5,6 +5,9 @@ mod paint;
 mod shape;
 mod text;
 
+use std::num::NonZeroU32;
+
+use rayon::prelude::*;
 use tiny_skia as sk;
 use typst::layout::{
     Abs, Axes, Frame, FrameItem, FrameKind, GroupItem, Point, Size, Transform,
@@ -12,23 +15,283 @@ use typst::layout::{
 use typst::model::Document;
 use typst::visualize::Color;
 
+/// Pages with more pixels than this at the supersampled resolution are
+/// rendered in multiple horizontal bands.
+const BAND_THRESHOLD: u32 = 4096 * 4096;
+
+/// The height of a band in pixels at the supersampled resolution.
+///
+/// This is fixed instead of depending on the number of threads so that the
+/// output is the same on every machine: Shapes that cross a band boundary are
+/// anti-aliased slightly differently than when rendered in one piece.
+const BAND_HEIGHT: u32 = 1024;
+
+/// Options that control the quality of rendering.
+#[derive(Debug, Copy, Clone, Eq, PartialEq, Hash)]
+pub struct RenderOptions {
+    /// How many samples to take per pixel along each axis.
+    ///
+    /// Values above one render the frame at a multiple of the resolution and
+    /// then scale it down. This gives smoother edges, in particular for small
+    /// text, at the cost of time and memory.
+    pub supersampling: NonZeroU32,
+    /// Whether to average supersampled pixels in linear light instead of in
+    /// sRGB space.
+    ///
+    /// This keeps thin dark strokes on light backgrounds (and vice versa)
+    /// from looking too thin or too bold. Has no effect without
+    /// supersampling.
+    pub gamma_correct: bool,
+    /// Text whose font size is below this many pixels is drawn as a
+    /// placeholder bar instead of glyph by glyph.
+    ///
+    /// At small sizes, text is illegible anyway and the bars look much the
+    /// same, but are a lot faster to draw. This is useful for thumbnails of
+    /// many pages. Zero disables placeholders.
+    pub text_placeholder_below: u32,
+}
+
+impl RenderOptions {
+    /// Options for fast, low-resolution previews of pages.
+    pub fn thumbnail() -> Self {
+        Self {
+            supersampling: NonZeroU32::MIN,
+            gamma_correct: false,
+            text_placeholder_below: 4,
+        }
+    }
+}
+
+impl Default for RenderOptions {
+    fn default() -> Self {
+        Self {
+            supersampling: NonZeroU32::MIN,
+            gamma_correct: true,
+            text_placeholder_below: 0,
+        }
+    }
+}
+
 /// Export a frame into a raster image.
 ///
 /// This renders the frame at the given number of pixels per point and returns
 /// the resulting `tiny-skia` pixel buffer.
 #[typst_macros::time(name = "render")]
 pub fn render(frame: &Frame, pixel_per_pt: f32, fill: Color) -> sk::Pixmap {
+    render_with(frame, pixel_per_pt, fill, RenderOptions::default())
+}
+
+/// Export a frame into a raster image with custom quality options.
+pub fn render_with(
+    frame: &Frame,
+    pixel_per_pt: f32,
+    fill: Color,
+    options: RenderOptions,
+) -> sk::Pixmap {
+    let factor = options.supersampling.get();
+    let placeholder = options.text_placeholder_below as f32;
+    let (pxw, pxh) = pixel_size(frame, pixel_per_pt);
+    let mut canvas = sk::Pixmap::new(pxw, pxh).unwrap();
+
+    let (lw, lh) = pixel_size(frame, pixel_per_pt * factor as f32);
+    let huge = lw.saturating_mul(lh) > BAND_THRESHOLD;
+    if factor == 1 && !huge {
+        canvas.fill(paint::to_sk_color(fill));
+        draw_band(&mut canvas.as_mut(), frame, pixel_per_pt, 0, placeholder, None);
+        return canvas;
+    }
+
+    // Supersampled and huge pages are rendered in horizontal bands. Each band
+    // is drawn into a pixmap of its own at the supersampled resolution and then
+    // scaled down into its rows of the canvas. Pages that are small enough form
+    // a single band. Huge pages are split into many, so that we never need
+    // memory for the whole page at the supersampled resolution and the bands
+    // can be rendered in parallel. The bounding boxes of the frame's items are
+    // then computed once up front, so that each band can cheaply skip the
+    // items outside of it.
+    let band_height = if huge { (BAND_HEIGHT / factor).max(1) } else { pxh };
+    let bounds = huge.then(|| Bounds::new(frame));
+    canvas
+        .data_mut()
+        .par_chunks_mut((band_height * pxw * 4) as usize)
+        .enumerate()
+        .for_each(|(i, rows)| {
+            let y = i as u32 * band_height * factor;
+            let height = (rows.len() as u32 / (pxw * 4) * factor)
+                .min(lh.saturating_sub(y))
+                .max(1);
+            let mut band = sk::Pixmap::new(lw, height).unwrap();
+            band.fill(paint::to_sk_color(fill));
+            draw_band(
+                &mut band.as_mut(),
+                frame,
+                pixel_per_pt * factor as f32,
+                y,
+                placeholder * factor as f32,
+                bounds.as_ref(),
+            );
+
+            if factor == 1 {
+                rows.copy_from_slice(band.data());
+            } else {
+                let height = rows.len() as u32 / (pxw * 4);
+                let mut rows = sk::PixmapMut::from_bytes(rows, pxw, height).unwrap();
+                downsample(&band, &mut rows, factor, options.gamma_correct);
+            }
+        });
+
+    canvas
+}
+
+/// The size of the pixmap a frame is rendered into.
+fn pixel_size(frame: &Frame, pixel_per_pt: f32) -> (u32, u32) {
     let size = frame.size();
     let pxw = (pixel_per_pt * size.x.to_f32()).round().max(1.0) as u32;
     let pxh = (pixel_per_pt * size.y.to_f32()).round().max(1.0) as u32;
+    (pxw, pxh)
+}
 
-    let mut canvas = sk::Pixmap::new(pxw, pxh).unwrap();
-    canvas.fill(paint::to_sk_color(fill));
+/// Scale a supersampled pixmap down into the target by averaging blocks of
+/// `factor` by `factor` pixels.
+fn downsample(
+    large: &sk::Pixmap,
+    canvas: &mut sk::PixmapMut,
+    factor: u32,
+    gamma_correct: bool,
+) {
+    let (pxw, pxh) = (canvas.width(), canvas.height());
+    let src = large.pixels();
+    let (lw, lh) = (large.width(), large.height());
 
-    let ts = sk::Transform::from_scale(pixel_per_pt, pixel_per_pt);
-    render_frame(&mut canvas, State::new(size, ts, pixel_per_pt), frame);
+    for y in 0..pxh {
+        for x in 0..pxw {
+            let mut sum = [0.0_f32; 4];
+            let mut n = 0.0;
+            for sy in (y * factor..(y + 1) * factor).take_while(|&sy| sy < lh) {
+                for sx in (x * factor..(x + 1) * factor).take_while(|&sx| sx < lw) {
+                    let c = src[(sy * lw + sx) as usize].demultiply();
+                    let a = c.alpha() as f32 / 255.0;
+                    for (s, v) in sum.iter_mut().zip([c.red(), c.green(), c.blue()]) {
+                        let v = v as f32 / 255.0;
+                        *s += a * if gamma_correct { to_linear(v) } else { v };
+                    }
+                    sum[3] += a;
+                    n += 1.0;
+                }
+            }
 
-    canvas
+            if n == 0.0 || sum[3] == 0.0 {
+                continue;
+            }
+
+            let channel = |s: f32| {
+                let v = s / sum[3];
+                let v = if gamma_correct { to_srgb(v) } else { v };
+                (v * 255.0).round() as u8
+            };
+            let alpha = (sum[3] / n * 255.0).round() as u8;
+            let color = sk::ColorU8::from_rgba(
+                channel(sum[0]),
+                channel(sum[1]),
+                channel(sum[2]),
+                alpha,
+            );
+            canvas.pixels_mut()[(y * pxw + x) as usize] = color.premultiply();
+        }
+    }
+}
+
+/// Convert an sRGB-encoded channel value to linear light.
+fn to_linear(v: f32) -> f32 {
+    if v <= 0.04045 {
+        v / 12.92
+    } else {
+        ((v + 0.055) / 1.055).powf(2.4)
+    }
+}
+
+/// Convert a linear channel value to sRGB encoding.
+fn to_srgb(v: f32) -> f32 {
+    if v <= 0.0031308 {
+        v * 12.92
+    } else {
+        1.055 * v.powf(1.0 / 2.4) - 0.055
+    }
+}
+
+/// Render the rows of a frame starting at pixel row `y` into the band.
+///
+/// The band may be shorter than the frame. Items that lie completely outside
+/// of it are skipped. This way, huge pages can be rendered piece by piece into
+/// a buffer of the caller's choosing, for example to stream the rows into an
+/// encoder without ever holding the whole page in memory.
+pub fn render_band(band: &mut sk::PixmapMut, frame: &Frame, pixel_per_pt: f32, y: u32) {
+    let partial =
+        y > 0 || (band.height() as f32) < frame.height().to_f32() * pixel_per_pt;
+    let bounds = partial.then(|| Bounds::new(frame));
+    draw_band(band, frame, pixel_per_pt, y, 0.0, bounds.as_ref());
+}
+
+/// Render the rows of a frame into the band, drawing text below the given
+/// pixel size as placeholders and skipping items outside of the band if their
+/// bounds are given.
+fn draw_band(
+    band: &mut sk::PixmapMut,
+    frame: &Frame,
+    pixel_per_pt: f32,
+    y: u32,
+    placeholder: f32,
+    bounds: Option<&Bounds>,
+) {
+    let ts = sk::Transform::from_scale(pixel_per_pt, pixel_per_pt)
+        .post_translate(0.0, -(y as f32));
+    let state = State {
+        text_placeholder_below: placeholder,
+        ..State::new(frame.size(), ts, pixel_per_pt)
+    };
+    render_frame_in(band, state, frame, bounds);
+}
+
+/// The bounding boxes of a frame's items, computed once for all bands.
+struct Bounds {
+    /// The bounding box of each item.
+    boxes: Vec<Option<(Point, Point)>>,
+    /// For each group item, the bounds of the items in its frame.
+    groups: Vec<Option<Bounds>>,
+}
+
+impl Bounds {
+    /// Compute the bounds of all items in the frame and its groups.
+    fn new(frame: &Frame) -> Self {
+        let mut boxes = Vec::with_capacity(frame.items().len());
+        let mut groups = Vec::with_capacity(frame.items().len());
+        for (_, item) in frame.items() {
+            if let FrameItem::Group(group) = item {
+                let inner = Self::new(&group.frame);
+                boxes.push(
+                    inner
+                        .bounding_box(&group.frame)
+                        .and_then(|bbox| group.outer_bounding_box(bbox)),
+                );
+                groups.push(Some(inner));
+            } else {
+                boxes.push(item.bounding_box());
+                groups.push(None);
+            }
+        }
+        Self { boxes, groups }
+    }
+
+    /// The bounding box of all items in the frame.
+    fn bounding_box(&self, frame: &Frame) -> Option<(Point, Point)> {
+        frame
+            .items()
+            .zip(&self.boxes)
+            .filter_map(|((pos, _), bbox)| {
+                bbox.map(|(min, max)| (*pos + min, *pos + max))
+            })
+            .reduce(|(a_min, a_max), (b_min, b_max)| (a_min.min(b_min), a_max.max(b_max)))
+    }
 }
 
 /// Export a document with potentially multiple pages into a single raster image.
@@ -43,7 +306,7 @@ pub fn render_merged(
 ) -> sk::Pixmap {
     let pixmaps: Vec<_> = document
         .pages
-        .iter()
+        .par_iter()
         .map(|page| render(&page.frame, pixel_per_pt, frame_fill))
         .collect();
 
@@ -85,6 +348,8 @@ struct State<'a> {
     pixel_per_pt: f32,
     /// The size of the first hard frame in the hierarchy.
     size: Size,
+    /// The pixel size below which text is drawn as a placeholder.
+    text_placeholder_below: f32,
 }
 
 impl<'a> State<'a> {
@@ -146,14 +411,40 @@ impl<'a> State<'a> {
 }
 
 /// Render a frame into the canvas.
-fn render_frame(canvas: &mut sk::Pixmap, state: State, frame: &Frame) {
-    for (pos, item) in frame.items() {
+fn render_frame(canvas: &mut sk::PixmapMut, state: State, frame: &Frame) {
+    render_frame_in(canvas, state, frame, None);
+}
+
+/// Render a frame into the canvas, skipping items that lie outside of the
+/// canvas' rows if their bounds are given.
+fn render_frame_in(
+    canvas: &mut sk::PixmapMut,
+    state: State,
+    frame: &Frame,
+    bounds: Option<&Bounds>,
+) {
+    for (i, (pos, item)) in frame.items().enumerate() {
+        let mut inner = None;
+        if let Some(bounds) = bounds {
+            if !covers_rows(canvas, state.pre_translate(*pos), bounds.boxes[i]) {
+                continue;
+            }
+            inner = bounds.groups[i].as_ref();
+        }
+
         match item {
             FrameItem::Group(group) => {
-                render_group(canvas, state, *pos, group);
+                render_group(canvas, state, *pos, group, inner);
             }
             FrameItem::Text(text) => {
-                text::render_text(canvas, state.pre_translate(*pos), text);
+                let state = state.pre_translate(*pos);
+                let ts = state.transform;
+                let pixel_size = text.size.to_f32() * ts.kx.hypot(ts.sy);
+                if pixel_size < state.text_placeholder_below {
+                    text::render_text_placeholder(canvas, state, text);
+                } else {
+                    text::render_text(canvas, state, text);
+                }
             }
             FrameItem::Shape(shape, _) => {
                 shape::render_shape(canvas, state.pre_translate(*pos), shape);
@@ -167,8 +458,36 @@ fn render_frame(canvas: &mut sk::Pixmap, state: State, frame: &Frame) {
     }
 }
 
+/// Whether an item with the given bounding box may cover any of the canvas'
+/// rows.
+fn covers_rows(
+    canvas: &sk::PixmapMut,
+    state: State,
+    bbox: Option<(Point, Point)>,
+) -> bool {
+    let Some((min, max)) = bbox else { return false };
+    let mut corners = [
+        sk::Point::from_xy(min.x.to_f32(), min.y.to_f32()),
+        sk::Point::from_xy(max.x.to_f32(), min.y.to_f32()),
+        sk::Point::from_xy(min.x.to_f32(), max.y.to_f32()),
+        sk::Point::from_xy(max.x.to_f32(), max.y.to_f32()),
+    ];
+    state.transform.map_points(&mut corners);
+    let top = corners.iter().map(|p| p.y).fold(f32::INFINITY, f32::min);
+    let bottom = corners.iter().map(|p| p.y).fold(f32::NEG_INFINITY, f32::max);
+
+    // Leave a pixel of room for anti-aliasing.
+    bottom >= -1.0 && top <= canvas.height() as f32 + 1.0
+}
+
 /// Render a group frame with optional transform and clipping into the canvas.
-fn render_group(canvas: &mut sk::Pixmap, state: State, pos: Point, group: &GroupItem) {
+fn render_group(
+    canvas: &mut sk::PixmapMut,
+    state: State,
+    pos: Point,
+    group: &GroupItem,
+    bounds: Option<&Bounds>,
+) {
     let sk_transform = to_sk_transform(&group.transform);
     let state = match group.frame.kind() {
         FrameKind::Soft => state.pre_translate(pos).pre_concat(sk_transform),
@@ -222,7 +541,7 @@ fn render_group(canvas: &mut sk::Pixmap, state: State, pos: Point, group: &Group
         }
     }
 
-    render_frame(canvas, state.with_mask(mask), &group.frame);
+    render_frame_in(canvas, state.with_mask(mask), &group.frame, bounds);
 }
 
 fn to_sk_transform(transform: &Transform) -> sk::Transform {
@@ -248,3 +567,121 @@ impl AbsExt for Abs {
         self.to_pt() as f32
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use typst::foundations::Bytes;
+    use typst::layout::Em;
+    use typst::syntax::Span;
+    use typst::text::{Font, Glyph, Lang, TextItem};
+    use typst::visualize::{Geometry, Paint};
+
+    use super::*;
+
+    /// A white frame with a black rectangle of the given width on its left.
+    fn frame_with_rect(size: Size, width: f64) -> Frame {
+        let mut frame = Frame::hard(size);
+        let shape =
+            Geometry::Rect(Size::new(Abs::pt(width), size.y)).filled(Color::BLACK.into());
+        frame.push(Point::zero(), FrameItem::Shape(shape, Span::detached()));
+        frame
+    }
+
+    /// The red channel of the pixel, which is enough for grayscale images.
+    fn gray(pixmap: &sk::Pixmap, x: u32, y: u32) -> u8 {
+        pixmap.pixel(x, y).unwrap().demultiply().red()
+    }
+
+    #[test]
+    fn test_render_supersampling() {
+        // A quarter of the second pixel is covered.
+        let frame = frame_with_rect(Size::new(Abs::pt(2.0), Abs::pt(1.0)), 1.25);
+        let options = RenderOptions {
+            supersampling: NonZeroU32::new(4).unwrap(),
+            gamma_correct: false,
+            ..RenderOptions::default()
+        };
+        let pixmap = render_with(&frame, 1.0, Color::WHITE, options);
+        assert_eq!((pixmap.width(), pixmap.height()), (2, 1));
+        assert_eq!(gray(&pixmap, 0, 0), 0);
+        assert_eq!(gray(&pixmap, 1, 0), 191);
+    }
+
+    #[test]
+    fn test_render_gamma_correction() {
+        let frame = frame_with_rect(Size::new(Abs::pt(2.0), Abs::pt(1.0)), 1.25);
+        let options = RenderOptions {
+            supersampling: NonZeroU32::new(4).unwrap(),
+            gamma_correct: true,
+            ..RenderOptions::default()
+        };
+        let pixmap = render_with(&frame, 1.0, Color::WHITE, options);
+        assert_eq!(gray(&pixmap, 0, 0), 0);
+        assert_eq!(gray(&pixmap, 1, 0), 225);
+    }
+
+    #[test]
+    fn test_render_bands() {
+        // A page that is large enough to be rendered in bands, with shapes in
+        // and across several of them.
+        let size = Size::new(Abs::pt(1000.0), Abs::pt(17000.0));
+        let mut frame = frame_with_rect(size, 10.0);
+        let rect = |h| Geometry::Rect(Size::new(Abs::pt(500.0), Abs::pt(h)));
+        for y in [100.0, 1000.0, 5000.0, 16900.0] {
+            let shape = rect(100.0).filled(Color::BLACK.into());
+            frame.push(
+                Point::with_y(Abs::pt(y)),
+                FrameItem::Shape(shape, Span::detached()),
+            );
+        }
+        let mut group = Frame::soft(Size::new(Abs::pt(500.0), Abs::pt(3000.0)));
+        let shape = rect(3000.0).filled(Color::BLACK.into());
+        group.push(Point::zero(), FrameItem::Shape(shape, Span::detached()));
+        frame.push_frame(Point::new(Abs::pt(500.0), Abs::pt(8000.0)), group);
+
+        let banded = render(&frame, 1.0, Color::WHITE);
+        let mut whole = sk::Pixmap::new(1000, 17000).unwrap();
+        whole.fill(sk::Color::WHITE);
+        render_band(&mut whole.as_mut(), &frame, 1.0, 0);
+        assert!(banded.data() == whole.data());
+        assert_eq!(gray(&banded, 600, 9000), 0);
+        assert_eq!(gray(&banded, 600, 11500), 255);
+    }
+
+    #[test]
+    fn test_render_text_placeholder() {
+        let data = typst_dev_assets::fonts().next().unwrap();
+        let font = Font::new(Bytes::from_static(data), 0).unwrap();
+        let text = TextItem {
+            font,
+            size: Abs::pt(20.0),
+            fill: Paint::Solid(Color::BLACK),
+            stroke: None,
+            lang: Lang::ENGLISH,
+            region: None,
+            text: "I".into(),
+            glyphs: vec![Glyph {
+                id: 1,
+                x_advance: Em::one(),
+                x_offset: Em::zero(),
+                range: 0..1,
+                span: (Span::detached(), 0),
+            }],
+        };
+        let mut frame = Frame::hard(Size::new(Abs::pt(40.0), Abs::pt(30.0)));
+        frame.push(Point::with_y(Abs::pt(20.0)), FrameItem::Text(text));
+
+        // The bar spans the text's advance and half of its size above the
+        // baseline, in a lighter shade.
+        let options = RenderOptions { text_placeholder_below: 40, ..Default::default() };
+        let pixmap = render_with(&frame, 1.0, Color::WHITE, options);
+        assert!((126..=129).contains(&gray(&pixmap, 5, 15)));
+        assert_eq!(gray(&pixmap, 5, 5), 255);
+        assert_eq!(gray(&pixmap, 30, 15), 255);
+
+        // Above the threshold, the glyphs are drawn as usual.
+        let options = RenderOptions { text_placeholder_below: 10, ..Default::default() };
+        let glyphs = render_with(&frame, 1.0, Color::WHITE, options);
+        assert_ne!(glyphs.data(), pixmap.data());
+    }
+}
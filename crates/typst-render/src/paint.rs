@@ -262,6 +262,6 @@ pub fn render_pattern_frame(state: &State, pattern: &Pattern) -> sk::Pixmap {
     // Render the pattern into a new canvas.
     let ts = sk::Transform::from_scale(state.pixel_per_pt, state.pixel_per_pt);
     let temp_state = State::new(pattern.size(), ts, state.pixel_per_pt);
-    crate::render_frame(&mut canvas, temp_state, pattern.frame());
+    crate::render_frame(&mut canvas.as_mut(), temp_state, pattern.frame());
     canvas
 }
@@ -7,7 +7,11 @@ use typst::visualize::{
 use crate::{paint, AbsExt, State};
 
 /// Render a geometrical shape into the canvas.
-pub fn render_shape(canvas: &mut sk::Pixmap, state: State, shape: &Shape) -> Option<()> {
+pub fn render_shape(
+    canvas: &mut sk::PixmapMut,
+    state: State,
+    shape: &Shape,
+) -> Option<()> {
     let ts = state.transform;
     let path = match shape.geometry {
         Geometry::Line(target) => {
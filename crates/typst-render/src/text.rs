@@ -6,13 +6,13 @@ use ttf_parser::{GlyphId, OutlineBuilder};
 use typst::layout::{Abs, Axes, Point, Size};
 use typst::text::color::{frame_for_glyph, is_color_glyph};
 use typst::text::{Font, TextItem};
-use typst::visualize::{FixedStroke, Paint};
+use typst::visualize::{FixedStroke, Geometry, Paint};
 
 use crate::paint::{self, GradientSampler, PaintSampler, PatternSampler};
 use crate::{shape, AbsExt, State};
 
 /// Render a text run into the canvas.
-pub fn render_text(canvas: &mut sk::Pixmap, state: State, text: &TextItem) {
+pub fn render_text(canvas: &mut sk::PixmapMut, state: State, text: &TextItem) {
     let mut x = 0.0;
     for glyph in &text.glyphs {
         let id = GlyphId(glyph.id);
@@ -38,9 +38,29 @@ pub fn render_text(canvas: &mut sk::Pixmap, state: State, text: &TextItem) {
     }
 }
 
+/// Render a text run as a bar that covers roughly its x-height.
+pub fn render_text_placeholder(
+    canvas: &mut sk::PixmapMut,
+    state: State,
+    text: &TextItem,
+) {
+    // The glyphs only cover part of their area, so a lighter bar is closer to
+    // the text's actual appearance.
+    let fill = match &text.fill {
+        Paint::Solid(color) => {
+            Paint::Solid(color.with_alpha(color.alpha().unwrap_or(1.0) * 0.5))
+        }
+        paint => paint.clone(),
+    };
+
+    let height = 0.5 * text.size;
+    let shape = Geometry::Rect(Size::new(text.width(), height)).filled(fill);
+    shape::render_shape(canvas, state.pre_translate(Point::with_y(-height)), &shape);
+}
+
 /// Render an outline glyph into the canvas. This is the "normal" case.
 fn render_outline_glyph(
-    canvas: &mut sk::Pixmap,
+    canvas: &mut sk::PixmapMut,
     state: State,
     text: &TextItem,
     id: GlyphId,
@@ -158,7 +178,7 @@ fn render_outline_glyph(
 }
 
 fn write_bitmap<S: PaintSampler>(
-    canvas: &mut sk::Pixmap,
+    canvas: &mut sk::PixmapMut,
     bitmap: &Bitmap,
     state: &State,
     sampler: S,
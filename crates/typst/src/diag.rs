@@ -138,6 +138,12 @@ pub struct SourceDiagnostic {
     /// Additional hints to the user, indicating how this problem could be avoided
     /// or worked around.
     pub hints: EcoVec<EcoString>,
+    /// A stable identifier for the kind of problem, like `"missing-glyph"`.
+    ///
+    /// In contrast to the message, this doesn't change between versions, so
+    /// tools can rely on it to single out certain diagnostics. Not all
+    /// diagnostics have one.
+    pub code: Option<&'static str>,
 }
 
 /// The severity of a [`SourceDiagnostic`].
@@ -158,6 +164,7 @@ impl SourceDiagnostic {
             trace: eco_vec![],
             message: message.into(),
             hints: eco_vec![],
+            code: None,
         }
     }
 
@@ -169,6 +176,7 @@ impl SourceDiagnostic {
             trace: eco_vec![],
             message: message.into(),
             hints: eco_vec![],
+            code: None,
         }
     }
 
@@ -188,6 +196,114 @@ impl SourceDiagnostic {
         self.hints.extend(hints);
         self
     }
+
+    /// Sets the diagnostic's stable identifier.
+    pub fn with_code(mut self, code: &'static str) -> Self {
+        self.code = Some(code);
+        self
+    }
+}
+
+/// Decides how warnings are reported, so that embedders can enforce quality
+/// gates, for example in CI.
+///
+/// Rules refer to warnings by their [code](SourceDiagnostic::code) or by a
+/// [class](Self::CLASSES) of related codes. A rule for a specific code takes
+/// precedence over a rule for its class, which in turn takes precedence over
+/// the rule for all warnings. Setting a rule again replaces the earlier one.
+/// Warnings that no rule applies to are kept.
+#[derive(Debug, Default, Clone)]
+pub struct WarningPolicy {
+    all: Option<WarningAction>,
+    classes: Vec<(EcoString, WarningAction)>,
+    codes: Vec<(EcoString, WarningAction)>,
+}
+
+/// What to do with a warning, as decided by a [`WarningPolicy`].
+#[derive(Debug, Copy, Clone, Eq, PartialEq, Hash)]
+pub enum WarningAction {
+    /// Suppress the warning.
+    Allow,
+    /// Keep the warning.
+    Warn,
+    /// Turn the warning into an error.
+    Deny,
+}
+
+impl WarningPolicy {
+    /// The classes that rules can refer to, with the codes they contain.
+    pub const CLASSES: &'static [(&'static str, &'static [&'static str])] =
+        &[("overflow", &["block-overflow"])];
+
+    /// Create a policy without any rules.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Set the rule for warnings with the given code.
+    pub fn code(&mut self, code: impl Into<EcoString>, action: WarningAction) {
+        let code = code.into();
+        self.codes.retain(|(c, _)| *c != code);
+        self.codes.push((code, action));
+    }
+
+    /// Set the rule for warnings whose code belongs to the given class.
+    pub fn class(&mut self, class: impl Into<EcoString>, action: WarningAction) {
+        let class = class.into();
+        self.classes.retain(|(c, _)| *c != class);
+        self.classes.push((class, action));
+    }
+
+    /// Set the rule for all warnings that have no rule for their code or
+    /// class.
+    pub fn all(&mut self, action: WarningAction) {
+        self.all = Some(action);
+    }
+
+    /// Determine what to do with a warning.
+    pub fn action(&self, warning: &SourceDiagnostic) -> WarningAction {
+        warning
+            .code
+            .and_then(|code| {
+                self.codes
+                    .iter()
+                    .find(|(c, _)| c == code)
+                    .or_else(|| {
+                        self.classes.iter().find(|(class, _)| {
+                            Self::CLASSES
+                                .iter()
+                                .any(|(c, codes)| c == class && codes.contains(&code))
+                        })
+                    })
+                    .map(|&(_, action)| action)
+            })
+            .or(self.all)
+            .unwrap_or(WarningAction::Warn)
+    }
+
+    /// Apply the policy to collected warnings.
+    ///
+    /// Removes suppressed and denied warnings from `warnings` and returns the
+    /// denied ones as errors.
+    pub fn apply(
+        &self,
+        warnings: &mut EcoVec<SourceDiagnostic>,
+    ) -> EcoVec<SourceDiagnostic> {
+        let mut errors = eco_vec![];
+        let mut kept = eco_vec![];
+        for warning in warnings.iter() {
+            match self.action(warning) {
+                WarningAction::Allow => {}
+                WarningAction::Warn => kept.push(warning.clone()),
+                WarningAction::Deny => errors.push(SourceDiagnostic {
+                    severity: Severity::Error,
+                    ..warning.clone()
+                }),
+            }
+        }
+        *warnings = kept;
+        errors
+    }
 }
 
 impl From<SyntaxError> for SourceDiagnostic {
@@ -198,6 +314,7 @@ impl From<SyntaxError> for SourceDiagnostic {
             message: error.message,
             trace: eco_vec![],
             hints: error.hints,
+            code: None,
         }
     }
 }
@@ -508,3 +625,68 @@ pub fn format_xml_like_error(format: &str, error: roxmltree::Error) -> EcoString
         err => eco_format!("failed to parse {format} ({err})"),
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn warning(code: Option<&'static str>) -> SourceDiagnostic {
+        let warning = SourceDiagnostic::warning(Span::detached(), "test");
+        match code {
+            Some(code) => warning.with_code(code),
+            None => warning,
+        }
+    }
+
+    #[test]
+    fn test_warning_policy_precedence() {
+        use WarningAction::*;
+
+        let overflow = warning(Some("block-overflow"));
+        let glyph = warning(Some("missing-glyph"));
+        let bare = warning(None);
+
+        let mut policy = WarningPolicy::new();
+        assert_eq!(policy.action(&overflow), Warn);
+
+        // A class rule beats the rule for all warnings, regardless of order.
+        policy.class("overflow", Deny);
+        policy.all(Allow);
+        assert_eq!(policy.action(&overflow), Deny);
+        assert_eq!(policy.action(&glyph), Allow);
+        assert_eq!(policy.action(&bare), Allow);
+
+        // A code rule beats its class rule, regardless of order.
+        policy.code("block-overflow", Warn);
+        policy.class("overflow", Allow);
+        assert_eq!(policy.action(&overflow), Warn);
+
+        // Setting a rule again replaces it.
+        policy.code("block-overflow", Deny);
+        assert_eq!(policy.action(&overflow), Deny);
+
+        // Rules for unknown classes and other codes don't apply.
+        let mut policy = WarningPolicy::new();
+        policy.class("glyphs", Deny);
+        policy.code("missing-glyphs", Deny);
+        assert_eq!(policy.action(&glyph), Warn);
+    }
+
+    #[test]
+    fn test_warning_policy_apply() {
+        let mut policy = WarningPolicy::new();
+        policy.class("overflow", WarningAction::Deny);
+        policy.code("missing-glyph", WarningAction::Allow);
+
+        let mut warnings = eco_vec![
+            warning(Some("block-overflow")),
+            warning(Some("missing-glyph")),
+            warning(None),
+        ];
+        let errors = policy.apply(&mut warnings);
+        assert_eq!(warnings.as_slice(), [warning(None)]);
+        assert_eq!(errors.len(), 1);
+        assert_eq!(errors[0].severity, Severity::Error);
+        assert_eq!(errors[0].code, Some("block-overflow"));
+    }
+}
@@ -815,6 +815,16 @@ impl Array {
     ///
     /// Returns an error if two values could not be compared or if the key
     /// function (if given) yields an error.
+    ///
+    /// ```example
+    /// #let people = (
+    ///   (name: "Emma", age: 31),
+    ///   (name: "Ali", age: 27),
+    ///   (name: "Zoe", age: 27),
+    /// )
+    ///
+    /// #people.sorted(key: p => p.age).map(p => p.name)
+    /// ```
     #[func]
     pub fn sorted(
         self,
@@ -830,31 +840,35 @@ impl Array {
         key: Option<Func>,
     ) -> SourceResult<Array> {
         let mut result = Ok(());
-        let mut vec = self.0;
-        let mut key_of = |x: Value| match &key {
-            // NOTE: We are relying on `comemo`'s memoization of function
-            // evaluation to not excessively reevaluate the `key`.
-            Some(f) => f.call(engine, context, [x]),
-            None => Ok(x),
-        };
-        vec.make_mut().sort_by(|a, b| {
-            // Until we get `try` blocks :)
-            match (key_of(a.clone()), key_of(b.clone())) {
-                (Ok(a), Ok(b)) => ops::compare(&a, &b).unwrap_or_else(|err| {
-                    if result.is_ok() {
-                        result = Err(err).at(span);
-                    }
-                    Ordering::Equal
-                }),
-                (Err(e), _) | (_, Err(e)) => {
-                    if result.is_ok() {
-                        result = Err(e);
-                    }
-                    Ordering::Equal
+        let mut compare = |a: &Value, b: &Value| {
+            ops::compare(a, b).unwrap_or_else(|err| {
+                if result.is_ok() {
+                    result = Err(err).at(span);
                 }
+                Ordering::Equal
+            })
+        };
+
+        let sorted = match key {
+            None => {
+                let mut vec = self.0;
+                vec.make_mut().sort_by(|a, b| compare(a, b));
+                vec
+            }
+            Some(f) => {
+                // Call the key function once per item instead of once per
+                // comparison.
+                let mut keyed = self
+                    .0
+                    .into_iter()
+                    .map(|x| Ok((f.call(engine, context, [x.clone()])?, x)))
+                    .collect::<SourceResult<Vec<_>>>()?;
+                keyed.sort_by(|(a, _), (b, _)| compare(a, b));
+                keyed.into_iter().map(|(_, x)| x).collect()
             }
-        });
-        result.map(|_| vec.into())
+        };
+
+        result.map(|_| sorted.into())
     }
 
     /// Deduplicates all items in the array.
@@ -128,7 +128,7 @@ impl Bytes {
     }
 
     /// Extracts a subslice of the bytes. Fails with an error if the start or
-    /// index is out of bounds.
+    /// end index is out of bounds.
     #[func]
     pub fn slice(
         &self,
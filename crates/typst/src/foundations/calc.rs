@@ -6,7 +6,7 @@ use std::ops::{Div, Rem};
 
 use crate::diag::{bail, At, SourceResult, StrResult};
 use crate::eval::ops;
-use crate::foundations::{cast, func, IntoValue, Module, Scope, Value};
+use crate::foundations::{cast, func, repr, IntoValue, Module, Scope, Str, Value};
 use crate::layout::{Angle, Fr, Length, Ratio};
 use crate::syntax::{Span, Spanned};
 
@@ -49,6 +49,7 @@ pub fn module() -> Module {
     scope.define_func::<div_euclid>();
     scope.define_func::<rem_euclid>();
     scope.define_func::<quo>();
+    scope.define_func::<format_number>();
     scope.define("inf", f64::INFINITY);
     scope.define("nan", f64::NAN);
     scope.define("pi", std::f64::consts::PI);
@@ -908,6 +909,83 @@ pub fn quo(
     Ok(floor(dividend.apply2(divisor.v, Div::div, Div::div)))
 }
 
+/// Formats a number as a string for display.
+///
+/// Unlike [`str`], this can pad or round the number to a fixed number of
+/// decimal places and group the digits of its integer part. This is useful
+/// for tables and other data-driven content.
+///
+/// ```example
+/// #calc.format-number(1234567.891, decimals: 2, thousands: ",") \
+/// #calc.format-number(0.5, decimals: 3, point: ",") \
+/// #calc.format-number(-42)
+/// ```
+#[func]
+pub fn format_number(
+    /// The number to format.
+    value: Spanned<Num>,
+    /// The number of decimal places to show.
+    ///
+    /// If this is `{none}`, integers are shown without decimal places and
+    /// floats with as many as needed to represent them exactly.
+    #[named]
+    decimals: Option<u8>,
+    /// The separator to insert between groups of three digits in the integer
+    /// part. If this is `{none}`, the digits are not grouped.
+    #[named]
+    thousands: Option<Str>,
+    /// The decimal separator.
+    #[named]
+    #[default(Str::from("."))]
+    point: Str,
+) -> SourceResult<Str> {
+    let (integer, fraction) = match value.v {
+        Num::Int(n) => {
+            (n.unsigned_abs().to_string(), "0".repeat(decimals.unwrap_or(0).into()))
+        }
+        Num::Float(n) if !n.is_finite() => {
+            bail!(value.span, "cannot format a number that is not finite")
+        }
+        Num::Float(n) => {
+            let formatted = match decimals {
+                Some(decimals) => format!("{:.*}", usize::from(decimals), n.abs()),
+                None => n.abs().to_string(),
+            };
+            match formatted.split_once('.') {
+                Some((integer, fraction)) => (integer.into(), fraction.into()),
+                None => (formatted, String::new()),
+            }
+        }
+    };
+
+    let mut output = String::new();
+
+    // Don't show a sign for numbers that were rounded to zero.
+    let negative = match value.v {
+        Num::Int(n) => n < 0,
+        Num::Float(n) => n < 0.0,
+    };
+    if negative && integer.bytes().chain(fraction.bytes()).any(|b| b != b'0') {
+        output.push_str(repr::MINUS_SIGN);
+    }
+
+    for (i, c) in integer.chars().enumerate() {
+        if i > 0 && (integer.len() - i) % 3 == 0 {
+            if let Some(thousands) = &thousands {
+                output.push_str(thousands);
+            }
+        }
+        output.push(c);
+    }
+
+    if !fraction.is_empty() {
+        output.push_str(&point);
+        output.push_str(&fraction);
+    }
+
+    Ok(output.into())
+}
+
 /// A value which can be passed to functions that work with integers and floats.
 #[derive(Debug, Copy, Clone)]
 pub enum Num {
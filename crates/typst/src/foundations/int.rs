@@ -3,7 +3,7 @@ use std::num::{NonZeroI64, NonZeroIsize, NonZeroU64, NonZeroUsize, ParseIntError
 use ecow::{eco_format, EcoString};
 
 use crate::{
-    diag::StrResult,
+    diag::{bail, StrResult},
     foundations::{cast, func, repr, scope, ty, Repr, Str, Value},
 };
 
@@ -36,7 +36,8 @@ impl i64 {
     /// Converts a value to an integer.
     ///
     /// - Booleans are converted to `0` or `1`.
-    /// - Floats are floored to the next 64-bit integer.
+    /// - Floats are truncated to the next 64-bit integer. Fails for NaN
+    ///   and for floats that are out of the range of integers.
     /// - Strings are parsed in base 10.
     ///
     /// ```example
@@ -231,10 +232,28 @@ cast! {
     ToInt,
     v: i64 => Self(v),
     v: bool => Self(v as i64),
-    v: f64 => Self(v as i64),
+    v: f64 => Self(convert_float_to_int(v)?),
     v: Str => Self(parse_int(&v).map_err(|_| eco_format!("invalid integer: {}", v))?),
 }
 
+/// Truncates a float to an integer, failing if it is not representable.
+fn convert_float_to_int(f: f64) -> StrResult<i64> {
+    if f.is_nan() {
+        bail!("cannot convert NaN to an integer");
+    }
+
+    // `i64::MIN` is -2^63 and thus exactly representable as a float, but
+    // `i64::MAX` rounds up to 2^63, which is out of range. Hence, the upper
+    // bound is exclusive.
+    const LIMIT: f64 = 9223372036854775808.0;
+    let truncated = f.trunc();
+    if truncated < -LIMIT || truncated >= LIMIT {
+        bail!("number too large");
+    }
+
+    Ok(truncated as i64)
+}
+
 fn parse_int(mut s: &str) -> Result<i64, ParseIntError> {
     let mut sign = 1;
     if let Some(rest) = s.strip_prefix('-').or_else(|| s.strip_prefix(repr::MINUS_SIGN)) {
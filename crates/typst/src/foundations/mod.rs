@@ -70,6 +70,7 @@ pub use {
     once_cell::sync::Lazy,
 };
 
+use comemo::Tracked;
 use ecow::EcoString;
 
 use crate::diag::{bail, SourceResult, StrResult};
@@ -109,6 +110,7 @@ pub(super) fn define(global: &mut Scope, inputs: Dict) {
     global.define_func::<repr::repr>();
     global.define_func::<panic>();
     global.define_func::<assert>();
+    global.define_func::<catch>();
     global.define_func::<eval>();
     global.define_func::<style>();
     global.define_module(calc::module());
@@ -243,6 +245,61 @@ impl assert {
     }
 }
 
+/// Calls a function and recovers from any error it produces.
+///
+/// This is useful for templates that should degrade gracefully, for example
+/// when an optional file may not exist. If the function succeeds, its result
+/// is returned. Otherwise, the `default` is returned instead.
+///
+/// Every error raised while calling the function is caught, not just those
+/// from [`panic`]($panic) and [`assert`]($assert). This includes failing file
+/// reads, type errors and even mistakes like a misspelled variable name, so
+/// keep the function as small as possible to avoid hiding bugs in your own
+/// code.
+///
+/// # Example
+/// ```example
+/// #let bio = catch(
+///   () => read("bio.txt"),
+///   default: [_No biography available._],
+/// )
+/// #bio
+/// ```
+#[func]
+pub fn catch(
+    /// The engine.
+    engine: &mut Engine,
+    /// The callsite context.
+    context: Tracked<Context>,
+    /// The function to call. It is called without arguments.
+    func: Func,
+    /// The value to return if the function fails.
+    ///
+    /// If this is a function, it is called with the message of the first
+    /// error and its result is returned instead.
+    ///
+    /// ```example
+    /// #catch(
+    ///   () => panic("oh no"),
+    ///   default: msg => [Recovered from: #msg],
+    /// )
+    /// ```
+    #[named]
+    #[default]
+    default: Value,
+) -> SourceResult<Value> {
+    match func.call(engine, context, std::iter::empty::<Value>()) {
+        Ok(value) => Ok(value),
+        Err(errors) => match default {
+            Value::Func(default) => {
+                let message = errors.first().map(|error| error.message.clone());
+                default.call(engine, context, [message.unwrap_or_default()])
+            }
+            default => Ok(default),
+        },
+    }
+}
+
 /// Evaluates a string as Typst code.
 ///
 /// This function should only be used as a last resort.
@@ -6,13 +6,14 @@ use std::ops::{Add, AddAssign, Deref, Range};
 use comemo::Tracked;
 use ecow::EcoString;
 use serde::{Deserialize, Serialize};
+use unicode_normalization::UnicodeNormalization;
 use unicode_segmentation::UnicodeSegmentation;
 
 use crate::diag::{bail, At, SourceResult, StrResult};
 use crate::engine::Engine;
 use crate::foundations::{
-    cast, dict, func, repr, scope, ty, Array, Bytes, Context, Dict, Func, IntoValue,
-    Label, Repr, Type, Value, Version,
+    cast, dict, func, repr, scope, ty, Array, Bytes, Cast, Context, Dict, Func,
+    IntoValue, Label, Repr, Type, Value, Version,
 };
 use crate::layout::Alignment;
 use crate::syntax::{Span, Spanned};
@@ -379,7 +380,8 @@ impl Str {
     /// - `captures`: An array containing a string for each matched capturing
     ///   group. The first item of the array contains the first matched
     ///   capturing, not the whole match! This is empty unless the `pattern` was
-    ///   a regex with capturing groups.
+    ///   a regex with capturing groups. Groups that did not participate in the
+    ///   match, like an optional group that was skipped, are `{none}`.
     #[func]
     pub fn match_(
         &self,
@@ -572,6 +574,39 @@ impl Str {
         trimmed.into()
     }
 
+    /// Pads the string with a fill character until it is at least the given
+    /// number of grapheme clusters long.
+    ///
+    /// ```example
+    /// #"42".pad(5, fill: "0", at: start) \
+    /// #"Chapter".pad(12, fill: ".")
+    /// ```
+    #[func]
+    pub fn pad(
+        &self,
+        /// The minimum length of the padded string, in grapheme clusters.
+        width: usize,
+        /// The character to pad with. Must be a single grapheme cluster.
+        #[named]
+        #[default(" ".into())]
+        fill: Str,
+        /// Can be `{start}` or `{end}` to specify the side to pad at. Defaults to
+        /// `{end}`.
+        #[named]
+        at: Option<StrSide>,
+    ) -> StrResult<Str> {
+        if fill.as_str().graphemes(true).count() != 1 {
+            bail!("fill must be exactly one grapheme cluster");
+        }
+
+        let len = self.as_str().graphemes(true).count();
+        let padding = fill.repeat(width.saturating_sub(len))?;
+        Ok(match at {
+            Some(StrSide::Start) => padding + self.clone(),
+            Some(StrSide::End) | None => self.clone() + padding,
+        })
+    }
+
     /// Splits a string at matches of a specified pattern and returns an array
     /// of the resulting parts.
     #[func]
@@ -602,6 +637,34 @@ impl Str {
         }
         s.into()
     }
+
+    /// Converts the string to a Unicode normal form.
+    ///
+    /// The same text can often be encoded in multiple ways. For example, an
+    /// accented letter can be a single codepoint or a letter followed by a
+    /// combining accent. Normalizing strings from external sources before
+    /// comparing or searching them makes sure that such differences don't
+    /// matter.
+    ///
+    /// ```example
+    /// #assert.eq("é".normalize(form: "nfd"), "e\u{0301}")
+    /// #assert.eq("ſ".normalize(form: "nfkc"), "s")
+    /// ```
+    #[func]
+    pub fn normalize(
+        &self,
+        /// The normal form to convert to.
+        #[named]
+        #[default(UnicodeNormalForm::Nfc)]
+        form: UnicodeNormalForm,
+    ) -> Str {
+        match form {
+            UnicodeNormalForm::Nfc => self.as_str().nfc().collect(),
+            UnicodeNormalForm::Nfd => self.as_str().nfd().collect(),
+            UnicodeNormalForm::Nfkc => self.as_str().nfkc().collect(),
+            UnicodeNormalForm::Nfkd => self.as_str().nfkd().collect(),
+        }
+    }
 }
 
 impl Deref for Str {
@@ -929,6 +992,23 @@ cast! {
     v: Regex => Self::Regex(v),
 }
 
+/// A Unicode normalization form.
+#[derive(Debug, Copy, Clone, Eq, PartialEq, Hash, Cast)]
+pub enum UnicodeNormalForm {
+    /// Canonical composition, where e.g. accented letters are turned into a
+    /// single codepoint.
+    Nfc,
+    /// Canonical decomposition, where e.g. accented letters are split into a
+    /// letter and a combining accent.
+    Nfd,
+    /// Like NFC, but also replaces compatibility characters like ligatures
+    /// with their plain equivalents.
+    Nfkc,
+    /// Like NFD, but also replaces compatibility characters like ligatures
+    /// with their plain equivalents.
+    Nfkd,
+}
+
 /// A side of a string.
 #[derive(Debug, Copy, Clone, Eq, PartialEq, Ord, PartialOrd)]
 pub enum StrSide {
@@ -1,6 +1,23 @@
 //! System-related things.
 
-use crate::foundations::{Dict, Module, Scope, Version};
+use crate::foundations::{Dict, IntoValue, Module, Scope, Version};
+
+/// Capabilities that templates can detect through `sys.features`.
+///
+/// Add an entry here when introducing something that templates might want to
+/// use conditionally, so that they don't have to hardcode a version.
+const FEATURES: &[&str] = &[
+    "font-info",
+    "glyph",
+    "image-crop",
+    "image-info",
+    "image-webp",
+    "labels",
+    "measure-size",
+    "pdf-embed",
+    "repeat-gap",
+    "stats",
+];
 
 /// A module with system-related things.
 pub fn module(inputs: Dict) -> Module {
@@ -14,5 +31,12 @@ pub fn module(inputs: Dict) -> Module {
         ]),
     );
     scope.define("inputs", inputs);
+    scope.define(
+        "features",
+        FEATURES
+            .iter()
+            .map(|&name| (name.into(), true.into_value()))
+            .collect::<Dict>(),
+    );
     Module::new("sys", scope)
 }
@@ -311,17 +311,22 @@ impl Counter {
         let mut stops = eco_vec![(state.clone(), page)];
 
         for elem in introspector.query(&self.selector()) {
-            if self.is_page() {
-                let prev = page;
-                page = introspector.page(elem.location().unwrap());
+            let prev = page;
+            page = introspector.page(elem.location().unwrap());
 
+            if self.is_page() {
                 let delta = page.get() - prev.get();
                 if delta > 0 {
                     state.step(NonZeroUsize::ONE, delta);
                 }
             }
 
-            if let Some(update) = match elem.with::<dyn Count>() {
+            let countable = elem.with::<dyn Count>();
+            if page > prev && countable.is_some_and(Count::restarts_per_page) {
+                state = CounterState::init(&self.0);
+            }
+
+            if let Some(update) = match countable {
                 Some(countable) => countable.update(),
                 None => Some(CounterUpdate::Step(NonZeroUsize::ONE)),
             } {
@@ -637,6 +642,12 @@ cast! {
 pub trait Count {
     /// Get the counter update for this element.
     fn update(&self) -> Option<CounterUpdate>;
+
+    /// Whether the counter should be reset before this element if it is on a
+    /// later page than the previous counter update.
+    fn restarts_per_page(&self) -> bool {
+        false
+    }
 }
 
 /// Counts through elements with different levels.
@@ -10,8 +10,10 @@ use smallvec::SmallVec;
 
 use crate::diag::{bail, StrResult};
 use crate::foundations::{Content, Label, Repr, Selector};
-use crate::introspection::Location;
-use crate::layout::{Frame, FrameItem, Page, Point, Position, Transform};
+use crate::introspection::{Location, TextCollector, TextStats};
+use crate::layout::{
+    Abs, Frame, FrameItem, Page, Point, Position, Sides, Size, Transform,
+};
 use crate::model::Numbering;
 use crate::utils::NonZeroExt;
 
@@ -27,6 +29,13 @@ pub struct Introspector {
     labels: HashMap<Label, SmallVec<[usize; 1]>>,
     /// The page numberings, indexed by page number minus 1.
     page_numberings: Vec<Option<Numbering>>,
+    /// The trimmed page sizes, resolved margins, and column counts, indexed
+    /// by page number minus 1.
+    page_geometries: Vec<(Size, Sides<Abs>, NonZeroUsize)>,
+    /// Statistics about the text of the whole document.
+    stats: TextStats,
+    /// Statistics about the text before each element, indexed like `elems`.
+    stats_before: Vec<TextStats>,
     /// Caches queries done on the introspector. This is important because
     /// even if all top-level queries are distinct, they often have shared
     /// subqueries. Example: Individual counter queries with `before` that
@@ -42,24 +51,38 @@ impl Introspector {
         self.elems.clear();
         self.labels.clear();
         self.page_numberings.clear();
+        self.page_geometries.clear();
+        self.stats_before.clear();
         self.queries.clear();
 
+        // Text continues across pages, so that words split by a page break
+        // are still counted once.
+        let mut text = TextCollector::default();
         for (i, page) in pages.iter().enumerate() {
             let page_nr = NonZeroUsize::new(1 + i).unwrap();
-            self.extract(&page.frame, page_nr, Transform::identity());
+            self.extract(&page.frame, page_nr, Transform::identity(), &mut text);
             self.page_numberings.push(page.numbering.clone());
+            self.page_geometries
+                .push((page.trim_size(), page.margin, page.columns));
         }
+        self.stats = text.flush();
     }
 
     /// Extract metadata from a frame.
-    fn extract(&mut self, frame: &Frame, page: NonZeroUsize, ts: Transform) {
+    fn extract(
+        &mut self,
+        frame: &Frame,
+        page: NonZeroUsize,
+        ts: Transform,
+        text: &mut TextCollector,
+    ) {
         for (pos, item) in frame.items() {
             match item {
                 FrameItem::Group(group) => {
                     let ts = ts
                         .pre_concat(Transform::translate(pos.x, pos.y))
                         .pre_concat(group.transform);
-                    self.extract(&group.frame, page, ts);
+                    self.extract(&group.frame, page, ts, text);
                 }
                 FrameItem::Tag(elem)
                     if !self.elems.contains_key(&elem.location().unwrap()) =>
@@ -70,12 +93,18 @@ impl Introspector {
                         (elem.clone(), Position { page, point: pos }),
                     );
                     assert!(ret.is_none(), "duplicate locations");
+                    self.stats_before.push(text.flush());
 
                     // Build the label cache.
                     if let Some(label) = elem.label() {
                         self.labels.entry(label).or_default().push(self.elems.len() - 1);
                     }
                 }
+                FrameItem::Text(item) => {
+                    let start = pos.transform(ts);
+                    let end = (*pos + Point::with_x(item.width())).transform(ts);
+                    text.push(&item.text, start, end);
+                }
                 _ => {}
             }
         }
@@ -86,6 +115,18 @@ impl Introspector {
         self.elems.values().map(|(c, _)| c)
     }
 
+    /// Iterate over the elements that are new or that changed their contents
+    /// or position compared to another introspector.
+    pub(crate) fn changed_since<'a>(
+        &'a self,
+        other: &'a Introspector,
+    ) -> impl Iterator<Item = &'a Content> + 'a {
+        self.elems
+            .iter()
+            .filter(|(loc, entry)| other.elems.get(*loc) != Some(*entry))
+            .map(|(_, (c, _))| c)
+    }
+
     /// Get an element by its location.
     fn get(&self, location: &Location) -> Option<&Content> {
         self.elems.get(location).map(|(elem, _)| elem)
@@ -263,6 +304,24 @@ impl Introspector {
         NonZeroUsize::new(self.pages).unwrap_or(NonZeroUsize::ONE)
     }
 
+    /// Statistics about the text of the whole document.
+    pub fn stats(&self) -> TextStats {
+        self.stats
+    }
+
+    /// Statistics about the text from the element at the given location up
+    /// to the next element matching the selector, or the end of the document.
+    pub fn section_stats(&self, location: Location, end: &Selector) -> Option<TextStats> {
+        let start = self.elems.get_index_of(&location)?;
+        let stop = self
+            .query(end)
+            .iter()
+            .map(|elem| self.index(elem))
+            .find(|&i| i > start && i != usize::MAX);
+        let after = stop.map_or(self.stats, |i| self.stats_before[i]);
+        Some(after - self.stats_before[start])
+    }
+
     /// Gets the page numbering for the given location, if any.
     pub fn page_numbering(&self, location: Location) -> Option<&Numbering> {
         let page = self.page(location);
@@ -271,6 +330,17 @@ impl Introspector {
             .and_then(|slot| slot.as_ref())
     }
 
+    /// Gets the trimmed size, resolved margins, and column count of the page
+    /// for the given location.
+    pub fn page_geometry(&self, location: Location) -> (Size, Sides<Abs>, NonZeroUsize) {
+        let page = self.page(location);
+        self.page_geometries.get(page.get() - 1).copied().unwrap_or((
+            Size::zero(),
+            Sides::default(),
+            NonZeroUsize::ONE,
+        ))
+    }
+
     /// Find the page number for the given location.
     pub fn page(&self, location: Location) -> NonZeroUsize {
         self.position(location).page
@@ -292,6 +362,9 @@ impl Default for Introspector {
             elems: IndexMap::new(),
             labels: HashMap::new(),
             page_numberings: vec![],
+            page_geometries: vec![],
+            stats: TextStats::default(),
+            stats_before: vec![],
             queries: QueryCache::default(),
         }
     }
@@ -3,7 +3,7 @@ use std::num::NonZeroUsize;
 use ecow::EcoString;
 
 use crate::engine::Engine;
-use crate::foundations::{func, scope, ty, Repr};
+use crate::foundations::{dict, func, scope, ty, Dict, Repr};
 use crate::layout::Position;
 use crate::model::Numbering;
 
@@ -78,6 +78,42 @@ impl Location {
         engine.introspector.position(self)
     }
 
+    /// Returns a dictionary with the size, the resolved margins, and the
+    /// number of columns of the page at this location. The dictionary has the
+    /// entries `width`, `height`, `margin`, and `columns`, where `margin` is a
+    /// dictionary with the entries `top`, `right`, `bottom`, and `left`. The
+    /// size is that of the trimmed page, without any
+    /// [bleed]($page.bleed) or crop marks.
+    ///
+    /// This is useful to compute sizes relative to the actual text area of the
+    /// page instead of hardcoding paper dimensions. To find out how much space
+    /// is available in the current container, use the
+    /// [`layout`]($layout) function instead.
+    ///
+    /// ```example
+    /// #set page(width: 160pt, height: 80pt, margin: (x: 20pt))
+    /// #context {
+    ///   let page = here().page-geometry()
+    ///   let area = page.width - page.margin.left - page.margin.right
+    ///   [The text area is #area wide.]
+    /// }
+    /// ```
+    #[func]
+    pub fn page_geometry(self, engine: &mut Engine) -> Dict {
+        let (size, margin, columns) = engine.introspector.page_geometry(self);
+        dict! {
+            "width" => size.x,
+            "height" => size.y,
+            "margin" => dict! {
+                "top" => margin.top,
+                "right" => margin.right,
+                "bottom" => margin.bottom,
+                "left" => margin.left,
+            },
+            "columns" => columns.get() as i64,
+        }
+    }
+
     /// Returns the page numbering pattern of the page at this location. This
     /// can be used when displaying the page counter in order to obtain the
     /// local numbering. This is useful if you are building custom indices or
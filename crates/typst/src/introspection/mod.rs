@@ -12,6 +12,8 @@ mod metadata;
 #[path = "query.rs"]
 mod query_;
 mod state;
+#[path = "stats.rs"]
+mod stats_;
 
 pub use self::counter::*;
 pub use self::here_::*;
@@ -22,6 +24,7 @@ pub use self::locator::*;
 pub use self::metadata::*;
 pub use self::query_::*;
 pub use self::state::*;
+pub use self::stats_::*;
 
 use crate::diag::{bail, SourceResult};
 use crate::engine::Engine;
@@ -54,6 +57,7 @@ pub fn define(global: &mut Scope) {
     global.define_func::<here>();
     global.define_func::<query>();
     global.define_func::<locate>();
+    global.define_func::<stats>();
 }
 
 /// Holds a locatable element that was realized.
@@ -0,0 +1,161 @@
+use std::ops::{AddAssign, Sub};
+
+use comemo::Tracked;
+use unicode_segmentation::UnicodeSegmentation;
+
+use crate::diag::{Hint, HintedStrResult};
+use crate::engine::Engine;
+use crate::foundations::{dict, func, Context, Dict, Selector};
+use crate::introspection::Location;
+use crate::layout::{Abs, Point};
+
+/// Provides statistics about the text of the document.
+///
+/// Returns a dictionary with the following keys:
+/// - `words`: The number of words.
+/// - `characters`: The number of characters, excluding whitespace.
+/// - `pages`: The number of pages.
+///
+/// The statistics are based on the laid out document, so they include
+/// everything that ends up on the pages, like headers, footers, and footnotes,
+/// but not [hidden]($hide) content. Like [`counter.final`]($counter.final),
+/// they refer to the final state of the document and thus need
+/// [context].
+///
+/// ```example
+/// #set page(footer: context [
+///   #stats().words words
+/// ])
+///
+/// = Introduction
+/// #lorem(20)
+/// ```
+///
+/// Words that are split across differently styled pieces of text, like
+/// `[#strong[bold]face]`, count as one word.
+///
+/// # Sections
+/// When given the location of an element, the word and character counts only
+/// cover the text from that element up to the next element of the same kind.
+/// For a heading, this is its section, which extends up to the next heading of
+/// any level.
+///
+/// ```example
+/// #show heading: it => {
+///   it
+///   context emph[
+///     About #stats(it.location()).words words
+///   ]
+/// }
+///
+/// = Introduction
+/// #lorem(20)
+///
+/// = Approach
+/// #lorem(50)
+/// ```
+#[func(contextual)]
+pub fn stats(
+    /// The engine.
+    engine: &mut Engine,
+    /// The callsite context.
+    context: Tracked<Context>,
+    /// The location of an element, like a heading, whose section to provide
+    /// statistics for.
+    #[default]
+    section: Option<Location>,
+) -> HintedStrResult<Dict> {
+    context.introspect()?;
+
+    let introspector = engine.introspector;
+    let stats = match section {
+        Some(location) => {
+            let elem = introspector
+                .query(&Selector::Location(location))
+                .first()
+                .cloned()
+                .ok_or("location does not belong to an element in the document")
+                .hint("try passing the location of a heading, e.g. `it.location()`")?;
+            let end = Selector::Elem(elem.elem(), None);
+            introspector.section_stats(location, &end).unwrap_or_default()
+        }
+        None => introspector.stats(),
+    };
+
+    Ok(dict! {
+        "words" => stats.words,
+        "characters" => stats.chars,
+        "pages" => introspector.pages().get(),
+    })
+}
+
+/// Statistics about the text in a document or part of it.
+#[derive(Debug, Default, Copy, Clone, Eq, PartialEq, Hash)]
+pub struct TextStats {
+    /// The number of words.
+    pub words: usize,
+    /// The number of characters, excluding whitespace.
+    pub chars: usize,
+}
+
+impl TextStats {
+    /// Compute the statistics for a piece of text.
+    pub fn of(text: &str) -> Self {
+        Self {
+            words: text.unicode_words().count(),
+            chars: text.chars().filter(|c| !c.is_whitespace()).count(),
+        }
+    }
+}
+
+impl AddAssign for TextStats {
+    fn add_assign(&mut self, rhs: Self) {
+        self.words += rhs.words;
+        self.chars += rhs.chars;
+    }
+}
+
+impl Sub for TextStats {
+    type Output = Self;
+
+    fn sub(self, rhs: Self) -> Self {
+        Self {
+            words: self.words - rhs.words,
+            chars: self.chars - rhs.chars,
+        }
+    }
+}
+
+/// Collects the text of a document in reading order.
+///
+/// Runs of text that directly continue each other on the same baseline are
+/// joined before counting, so that a word which is split across runs (for
+/// example because part of it is bold) isn't counted twice.
+#[derive(Default)]
+pub(crate) struct TextCollector {
+    /// The statistics of the text that was already counted.
+    stats: TextStats,
+    /// The joined text of the current runs.
+    pending: String,
+    /// Where the last run ended.
+    end: Option<Point>,
+}
+
+impl TextCollector {
+    /// Add a run of text that starts and ends at the given points.
+    pub fn push(&mut self, text: &str, start: Point, end: Point) {
+        if !self.end.is_some_and(|prev| (prev - start).hypot() < Abs::pt(0.01)) {
+            self.flush();
+        }
+        self.pending.push_str(text);
+        self.end = Some(end);
+    }
+
+    /// Count the pending text and return the statistics of all text so far.
+    pub fn flush(&mut self) -> TextStats {
+        self.stats += TextStats::of(&self.pending);
+        self.pending.clear();
+        self.end = None;
+        self.stats
+    }
+}
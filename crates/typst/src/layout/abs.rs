@@ -29,7 +29,8 @@ impl Abs {
 
     /// Create an absolute length from a value in a unit.
     pub fn with_unit(val: f64, unit: AbsUnit) -> Self {
-        Self(Scalar::new(val * unit.raw_scale()))
+        let (num, den) = unit.raw_scale();
+        Self(Scalar::new(val * num / den))
     }
 
     /// Create an absolute length from a number of points.
@@ -59,7 +60,8 @@ impl Abs {
 
     /// Get the value of this absolute length in a unit.
     pub fn to_unit(self, unit: AbsUnit) -> f64 {
-        self.to_raw() / unit.raw_scale()
+        let (num, den) = unit.raw_scale();
+        self.to_raw() * den / num
     }
 
     /// Convert this to a number of points.
@@ -246,13 +248,19 @@ pub enum AbsUnit {
 }
 
 impl AbsUnit {
-    /// How many raw units correspond to a value of `1.0` in this unit.
-    fn raw_scale(self) -> f64 {
+    /// How many raw units correspond to a value of `1.0` in this unit, as a
+    /// fraction.
+    ///
+    /// Keeping the numerator and denominator apart avoids rounding the
+    /// conversion factor. Both are integers and thus exact, so that equal
+    /// lengths in different units like `1in == 2.54cm` and `10mm == 1cm`
+    /// compare equal.
+    fn raw_scale(self) -> (f64, f64) {
         match self {
-            AbsUnit::Pt => 1.0,
-            AbsUnit::Mm => 2.83465,
-            AbsUnit::Cm => 28.3465,
-            AbsUnit::In => 72.0,
+            AbsUnit::Pt => (1.0, 1.0),
+            AbsUnit::Mm => (720.0, 254.0),
+            AbsUnit::Cm => (7200.0, 254.0),
+            AbsUnit::In => (72.0, 1.0),
         }
     }
 }
@@ -241,6 +241,26 @@ pub struct BlockElem {
     /// ```
     pub height: Smart<Rel<Length>>,
 
+    /// A [fraction]($fraction) of the space that remains in the region, which
+    /// the block expands to fill. When set, this takes precedence over the
+    /// [`height`]($block.height).
+    ///
+    /// This is useful for a notes area that fills the rest of a page, for
+    /// example. Multiple such blocks and [fractional spacings]($v) share the
+    /// remaining space proportionally. Outside of a page or another flow of
+    /// blocks, the block takes the full height of its region.
+    ///
+    /// ```example
+    /// #set page(height: 100pt)
+    /// = Notes
+    /// #block(
+    ///   width: 100%,
+    ///   rest: 1fr,
+    ///   stroke: (bottom: 0.5pt),
+    /// )
+    /// ```
+    pub rest: Option<Fr>,
+
     /// Whether the block can be broken and continue on the next page.
     ///
     /// ```example
@@ -332,17 +352,27 @@ pub struct BlockElem {
     #[default(false)]
     pub clip: bool,
 
-    /// The contents of the block.
-    #[positional]
-    pub body: Option<Content>,
-
     /// Whether this block must stick to the following one.
     ///
-    /// Use this to prevent page breaks between e.g. a heading and its body.
-    #[internal]
+    /// If the following block doesn't fit into the current region anymore,
+    /// a sticky block moves along with it into the next region. Use this to
+    /// prevent page breaks between e.g. a custom heading and its body.
+    /// Headings are sticky by default.
+    ///
+    /// ```example
+    /// #set page(height: 100pt)
+    /// #v(50pt)
+    /// #block(sticky: true)[*Summary*]
+    /// #block(breakable: false)[
+    ///   #lorem(12)
+    /// ]
+    /// ```
     #[default(false)]
-    #[ghost]
     pub sticky: bool,
+
+    /// The contents of the block.
+    #[positional]
+    pub body: Option<Content>,
 }
 
 impl LayoutMultiple for Packed<BlockElem> {
@@ -360,8 +390,16 @@ impl LayoutMultiple for Packed<BlockElem> {
             body = body.clone().padded(inset.map(|side| side.map(Length::from)));
         }
 
+        // A share of the remaining space is resolved by the flow, which then
+        // passes exactly that space. Elsewhere, the block takes the full
+        // height of the region.
+        let height = match self.rest(styles) {
+            Some(_) => Smart::Custom(Ratio::one().into()),
+            None => self.height(styles),
+        };
+
         // Resolve the sizing to a concrete size.
-        let sizing = Axes::new(self.width(styles), self.height(styles));
+        let sizing = Axes::new(self.width(styles), height);
         let mut expand = sizing.as_ref().map(Smart::is_custom);
         let mut size = sizing
             .resolve(styles)
@@ -6,7 +6,7 @@
 
 use std::fmt::{self, Debug, Formatter};
 
-use crate::diag::{bail, SourceResult};
+use crate::diag::{bail, warning, SourceResult};
 use crate::engine::Engine;
 use crate::foundations::{
     elem, Content, NativeElement, Packed, Resolve, Smart, StyleChain, StyledElem,
@@ -62,10 +62,10 @@ impl LayoutMultiple for Packed<FlowElem> {
                 .can::<dyn LayoutMultiple>();
         }
 
+        let outer = &styles;
         let mut layouter = FlowLayouter::new(regions, styles, alone);
         for mut child in self.children().iter() {
-            let outer = styles;
-            let mut styles = styles;
+            let mut styles = *outer;
             if let Some(styled) = child.to_packed::<StyledElem>() {
                 child = &styled.child;
                 styles = outer.chain(&styled.styles);
@@ -84,6 +84,11 @@ impl LayoutMultiple for Packed<FlowElem> {
                 }
             } else if let Some(elem) = child.to_packed::<ParElem>() {
                 layouter.layout_par(engine, elem, styles)?;
+            } else if let Some((elem, fr)) = child
+                .to_packed::<BlockElem>()
+                .and_then(|elem| Some((elem, elem.rest(styles)?)))
+            {
+                layouter.layout_fractional_block(engine, elem, fr, styles)?;
             } else if let Some(layoutable) = child.with::<dyn LayoutSingle>() {
                 layouter.layout_single(engine, layoutable, styles)?;
             } else if let Some(layoutable) = child.with::<dyn LayoutMultiple>() {
@@ -122,11 +127,11 @@ struct FlowLayouter<'a> {
     /// Used for indenting paragraphs after the first in a block.
     last_was_par: bool,
     /// Spacing and layouted blocks for the current region.
-    items: Vec<FlowItem>,
+    items: Vec<FlowItem<'a>>,
     /// A queue of tags that will be attached to the next frame.
     pending_tags: Vec<Content>,
     /// A queue of floating elements.
-    pending_floats: Vec<FlowItem>,
+    pending_floats: Vec<FlowItem<'a>>,
     /// Whether we have any footnotes in the current region.
     has_footnotes: bool,
     /// Footnote configuration.
@@ -144,11 +149,12 @@ struct FootnoteConfig {
 
 /// A prepared item in a flow layout.
 #[derive(Debug)]
-enum FlowItem {
+enum FlowItem<'a> {
     /// Spacing between other items and whether it is weak.
     Absolute(Abs, bool),
-    /// Fractional spacing between other items.
-    Fractional(Fr),
+    /// Fractional spacing between other items or a fractionally sized block
+    /// that is layouted once the remaining space is known.
+    Fractional(Fr, Option<(&'a Packed<BlockElem>, StyleChain<'a>)>),
     /// A frame for a layouted block.
     Frame {
         /// The frame itself.
@@ -175,7 +181,7 @@ enum FlowItem {
     Footnote(Frame),
 }
 
-impl FlowItem {
+impl FlowItem<'_> {
     /// Whether this item is out-of-flow.
     ///
     /// Out-of-flow items are guaranteed to have a [zero size][Size::zero()].
@@ -246,7 +252,7 @@ impl<'a> FlowLayouter<'a> {
                     rel.resolve(styles).relative_to(self.initial.y),
                     v.weakness(styles) > 0,
                 ),
-                Spacing::Fr(fr) => FlowItem::Fractional(*fr),
+                Spacing::Fr(fr) => FlowItem::Fractional(*fr, None),
             },
         )
     }
@@ -295,28 +301,7 @@ impl<'a> FlowLayouter<'a> {
         // If the first line doesn’t fit in this region, then defer any
         // previous sticky frame to the next region (if available)
         if let Some(first) = lines.first() {
-            while !self.regions.size.y.fits(first.height()) && !self.regions.in_last() {
-                let mut sticky = self.items.len();
-                for (i, item) in self.items.iter().enumerate().rev() {
-                    match *item {
-                        FlowItem::Absolute(_, _) => {}
-                        FlowItem::Frame { sticky: true, .. } => sticky = i,
-                        _ => break,
-                    }
-                }
-
-                let carry: Vec<_> = self.items.drain(sticky..).collect();
-                self.finish_region(engine, false)?;
-                let in_last = self.regions.in_last();
-
-                for item in carry {
-                    self.layout_item(engine, item)?;
-                }
-
-                if in_last {
-                    break;
-                }
-            }
+            self.carry_sticky(engine, first.height())?;
         }
 
         for (i, mut frame) in lines.into_iter().enumerate() {
@@ -335,6 +320,31 @@ impl<'a> FlowLayouter<'a> {
         Ok(())
     }
 
+    /// Layout a block that fills a share of the remaining space. Its layout is
+    /// deferred until the region is finished and the remaining space is known.
+    fn layout_fractional_block(
+        &mut self,
+        engine: &mut Engine,
+        block: &'a Packed<BlockElem>,
+        fr: Fr,
+        styles: StyleChain<'a>,
+    ) -> SourceResult<()> {
+        // Keep pending tags in front of the block.
+        if !self.pending_tags.is_empty() {
+            let mut frame = Frame::soft(Size::zero());
+            self.drain_tag_into(&mut frame);
+            let align = AlignElem::alignment_in(styles).resolve(styles);
+            self.layout_item(
+                engine,
+                FlowItem::Frame { frame, align, sticky: false, movable: false },
+            )?;
+        }
+
+        self.layout_item(engine, FlowItem::Fractional(fr, Some((block, styles))))?;
+        self.last_was_par = false;
+        Ok(())
+    }
+
     /// Layout into a single region.
     fn layout_single(
         &mut self,
@@ -346,6 +356,7 @@ impl<'a> FlowLayouter<'a> {
         let sticky = BlockElem::sticky_in(styles);
         let pod = Regions::one(self.regions.base(), Axes::splat(false));
         let mut frame = layoutable.layout(engine, styles, pod)?;
+        self.carry_sticky(engine, frame.height())?;
         self.drain_tag(&mut frame);
         frame.post_process(styles);
         self.layout_item(
@@ -389,9 +400,18 @@ impl<'a> FlowLayouter<'a> {
         .resolve(styles);
 
         // Layout the block itself.
-        let sticky = BlockElem::sticky_in(styles);
+        let sticky = match child.to_packed::<BlockElem>() {
+            Some(block) => block.sticky(styles),
+            None => BlockElem::sticky_in(styles),
+        };
         let fragment = layoutable.layout(engine, styles, self.regions)?;
 
+        // If the block doesn't start in this region, then defer any previous
+        // sticky frame to the next region (if available).
+        if let Some(first) = fragment.iter().next() {
+            self.carry_sticky(engine, first.height())?;
+        }
+
         for (i, mut frame) in fragment.into_iter().enumerate() {
             // Find footnotes in the frame.
             if self.root {
@@ -419,9 +439,46 @@ impl<'a> FlowLayouter<'a> {
         Ok(())
     }
 
+    /// If a frame of the given height doesn't fit into the current region,
+    /// finish the region and carry trailing sticky frames over to the next
+    /// one, so that they stay together with the frame.
+    fn carry_sticky(&mut self, engine: &mut Engine, height: Abs) -> SourceResult<()> {
+        while !self.regions.size.y.fits(height) && !self.regions.in_last() {
+            let mut sticky = self.items.len();
+            for (i, item) in self.items.iter().enumerate().rev() {
+                match *item {
+                    FlowItem::Absolute(_, _) => {}
+                    FlowItem::Frame { sticky: true, .. } => sticky = i,
+                    _ => break,
+                }
+            }
+
+            let carry: Vec<_> = self.items.drain(sticky..).collect();
+            self.finish_region(engine, false)?;
+            let in_last = self.regions.in_last();
+
+            for item in carry {
+                self.layout_item(engine, item)?;
+            }
+
+            if in_last {
+                break;
+            }
+        }
+
+        Ok(())
+    }
+
     /// Attach currently pending metadata to the frame.
     fn drain_tag(&mut self, frame: &mut Frame) {
-        if !self.pending_tags.is_empty() && !frame.is_empty() {
+        if !frame.is_empty() {
+            self.drain_tag_into(frame);
+        }
+    }
+
+    /// Attach currently pending metadata to the frame, even if it is empty.
+    fn drain_tag_into(&mut self, frame: &mut Frame) {
+        if !self.pending_tags.is_empty() {
             frame.prepend_multiple(
                 self.pending_tags
                     .drain(..)
@@ -434,21 +491,23 @@ impl<'a> FlowLayouter<'a> {
     fn layout_item(
         &mut self,
         engine: &mut Engine,
-        mut item: FlowItem,
+        mut item: FlowItem<'a>,
     ) -> SourceResult<()> {
         match item {
             FlowItem::Absolute(v, weak) => {
                 if weak
-                    && !self
-                        .items
-                        .iter()
-                        .any(|item| matches!(item, FlowItem::Frame { .. }))
+                    && !self.items.iter().any(|item| {
+                        matches!(
+                            item,
+                            FlowItem::Frame { .. } | FlowItem::Fractional(_, Some(_))
+                        )
+                    })
                 {
                     return Ok(());
                 }
                 self.regions.size.y -= v
             }
-            FlowItem::Fractional(_) => {}
+            FlowItem::Fractional(..) => {}
             FlowItem::Frame { ref frame, movable, .. } => {
                 let height = frame.height();
                 while !self.regions.size.y.fits(height) && !self.regions.in_last() {
@@ -563,7 +622,7 @@ impl<'a> FlowLayouter<'a> {
         for item in &self.items {
             match item {
                 FlowItem::Absolute(v, _) => used.y += *v,
-                FlowItem::Fractional(v) => fr += *v,
+                FlowItem::Fractional(v, _) => fr += *v,
                 FlowItem::Frame { frame, .. } => {
                     used.y += frame.height();
                     used.x.set_max(frame.width());
@@ -611,9 +670,32 @@ impl<'a> FlowLayouter<'a> {
                 FlowItem::Absolute(v, _) => {
                     offset += v;
                 }
-                FlowItem::Fractional(v) => {
+                FlowItem::Fractional(v, elem) => {
                     let remaining = self.initial.y - used.y;
-                    offset += v.share(fr, remaining);
+                    let length = v.share(fr, remaining);
+                    if let Some((block, styles)) = elem {
+                        let region = Size::new(size.x, length);
+                        let pod = Regions::one(region, Axes::new(self.expand.x, true));
+                        let mut frames =
+                            block.layout(engine, styles, pod)?.into_frames().into_iter();
+                        let mut frame = frames.next().unwrap_or_default();
+                        if frames.next().is_some() {
+                            engine.tracer.warn(
+                                warning!(
+                                    block.span(),
+                                    "block content does not fit into its share of the \
+                                     remaining space";
+                                    hint: "only the part that fits is shown",
+                                )
+                                .with_code("block-overflow"),
+                            );
+                        }
+                        frame.post_process(styles);
+                        let align = AlignElem::alignment_in(styles).resolve(styles);
+                        let x = align.x.position(size.x - frame.width());
+                        output.push_frame(Point::new(x, offset), frame);
+                    }
+                    offset += length;
                 }
                 FlowItem::Frame { frame, align, .. } => {
                     ruler = ruler.max(align.y);
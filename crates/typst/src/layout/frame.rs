@@ -4,19 +4,23 @@ use std::fmt::{self, Debug, Formatter};
 use std::num::NonZeroUsize;
 use std::sync::Arc;
 
+use ecow::EcoString;
 use smallvec::SmallVec;
+use ttf_parser::GlyphId;
 
+use crate::diag::{bail, At, SourceResult, StrResult};
 use crate::foundations::{cast, dict, Content, Dict, StyleChain, Value};
 use crate::layout::{
-    Abs, Axes, Corners, FixedAlignment, HideElem, Length, Point, Rel, Sides, Size,
+    Abs, Axes, Corners, Em, FixedAlignment, HideElem, Length, Point, Rel, Sides, Size,
     Transform,
 };
-use crate::model::{Destination, LinkElem};
+use crate::model::{Destination, LinkElem, PlaceholderElem};
 use crate::syntax::Span;
 use crate::text::TextItem;
 use crate::utils::{LazyHash, Numeric};
 use crate::visualize::{
-    ellipse, styled_rect, Color, FixedStroke, Geometry, Image, Paint, Path, Shape,
+    ellipse, styled_rect, Color, ColorTransform, FixedStroke, Geometry, Image, ImageKind,
+    Paint, Path, PathItem, Shape,
 };
 
 /// A finished layout with items at fixed positions.
@@ -138,6 +142,145 @@ impl Frame {
     pub fn items(&self) -> std::slice::Iter<'_, (Point, FrameItem)> {
         self.items.iter()
     }
+
+    /// The names and reserved sizes of all placeholder boxes
+    /// in this frame and its subframes, in order of appearance.
+    pub fn placeholders(&self) -> Vec<(EcoString, Size)> {
+        let mut found = vec![];
+        let mut pending = None;
+        for (_, item) in self.items() {
+            match item {
+                FrameItem::Tag(elem) => {
+                    pending = elem
+                        .to_packed::<PlaceholderElem>()
+                        .map(|elem| elem.name().clone());
+                    continue;
+                }
+                FrameItem::Group(group) => match pending {
+                    Some(name) => found.push((name, group.frame.size())),
+                    None => found.extend(group.frame.placeholders()),
+                },
+                _ => {}
+            }
+            pending = None;
+        }
+        found
+    }
+
+    /// Computes the bounding box of the frame's visible contents as its
+    /// top-left and bottom-right corner, relative to the frame's origin.
+    ///
+    /// In contrast to the frame's size, this accounts for transformations,
+    /// clipping, stroke thickness, and the actual ink extents of glyphs. The
+    /// result may thus also extend beyond the frame. Returns `None` if the
+    /// frame has no visible contents.
+    pub fn bounding_box(&self) -> Option<(Point, Point)> {
+        let mut bbox = None;
+        for (pos, item) in self.items() {
+            if let Some((min, max)) = item.bounding_box() {
+                bbox = Some(union(bbox, (*pos + min, *pos + max)));
+            }
+        }
+        bbox
+    }
+}
+
+/// The ink bounding box of a text item relative to its baseline origin.
+fn text_bounding_box(text: &TextItem) -> Option<(Point, Point)> {
+    let font = &text.font;
+    let to_abs = |units: i16| Em::from_units(units, font.units_per_em()).at(text.size);
+    let pad = text
+        .stroke
+        .as_ref()
+        .map_or(Abs::zero(), |stroke| stroke.thickness / 2.0);
+
+    let mut bbox = None;
+    let mut x = Abs::zero();
+    for glyph in &text.glyphs {
+        let dx = x + glyph.x_offset.at(text.size);
+        let (min, max) = match font.ttf().glyph_bounding_box(GlyphId(glyph.id)) {
+            Some(rect) => (
+                Point::new(dx + to_abs(rect.x_min), -to_abs(rect.y_max)),
+                Point::new(dx + to_abs(rect.x_max), -to_abs(rect.y_min)),
+            ),
+            // Bitmap glyphs have no outline, so we fall back to the font's
+            // vertical metrics.
+            None => {
+                let metrics = font.metrics();
+                (
+                    Point::new(dx, -metrics.ascender.at(text.size)),
+                    Point::new(
+                        dx + glyph.x_advance.at(text.size),
+                        -metrics.descender.at(text.size),
+                    ),
+                )
+            }
+        };
+
+        let padding = Point::new(pad, pad);
+        bbox = Some(union(bbox, (min - padding, max + padding)));
+        x += glyph.x_advance.at(text.size);
+    }
+
+    bbox
+}
+
+/// The bounding box of a shape including its stroke.
+fn shape_bounding_box(shape: &Shape) -> Option<(Point, Point)> {
+    let (min, max) = match &shape.geometry {
+        Geometry::Line(to) => (Point::zero().min(*to), Point::zero().max(*to)),
+        Geometry::Rect(size) => (Point::zero(), size.to_point()),
+        Geometry::Path(path) => path_bounding_box(path)?,
+    };
+
+    let pad = shape
+        .stroke
+        .as_ref()
+        .map_or(Abs::zero(), |stroke| stroke.thickness / 2.0);
+    let padding = Point::new(pad, pad);
+    Some((min - padding, max + padding))
+}
+
+/// The exact bounding box of a path, including its curves.
+fn path_bounding_box(path: &Path) -> Option<(Point, Point)> {
+    let to_kurbo = |p: &Point| kurbo::Point::new(p.x.to_pt(), p.y.to_pt());
+    let from_kurbo = |p: kurbo::Point| Point::new(Abs::pt(p.x), Abs::pt(p.y));
+
+    let mut bbox = None;
+    let mut cursor = Point::zero();
+    for item in &path.0 {
+        let (min, max) = match item {
+            PathItem::MoveTo(to) | PathItem::LineTo(to) => {
+                cursor = *to;
+                (*to, *to)
+            }
+            PathItem::CubicTo(c0, c1, end) => {
+                let cubic = kurbo::CubicBez::new(
+                    to_kurbo(&cursor),
+                    to_kurbo(c0),
+                    to_kurbo(c1),
+                    to_kurbo(end),
+                );
+                let rect = kurbo::Shape::bounding_box(&cubic);
+                cursor = *end;
+                (
+                    from_kurbo(rect.origin()),
+                    from_kurbo(kurbo::Point::new(rect.x1, rect.y1)),
+                )
+            }
+            PathItem::ClosePath => continue,
+        };
+        bbox = Some(union(bbox, (min, max)));
+    }
+    bbox
+}
+
+/// The union of two bounding boxes.
+fn union(a: Option<(Point, Point)>, b: (Point, Point)) -> (Point, Point) {
+    match a {
+        Some(a) => (a.0.min(b.0), a.1.max(b.1)),
+        None => b,
+    }
 }
 
 /// Insert items and subframes.
@@ -387,6 +530,128 @@ impl Frame {
         }
     }
 
+    /// Apply a color transformation to all colors in the frame.
+    ///
+    /// This affects text, shapes, and raster images, including the contents
+    /// of patterns. SVG images are left unchanged.
+    pub fn map_colors(&mut self, transform: ColorTransform) {
+        for (_, item) in Arc::make_mut(&mut self.items).iter_mut() {
+            match item {
+                FrameItem::Group(group) => group.frame.map_colors(transform),
+                FrameItem::Text(text) => {
+                    text.fill = text.fill.map_colors(transform);
+                    if let Some(stroke) = &mut text.stroke {
+                        stroke.paint = stroke.paint.map_colors(transform);
+                    }
+                }
+                FrameItem::Shape(shape, _) => {
+                    if let Some(fill) = &mut shape.fill {
+                        *fill = fill.map_colors(transform);
+                    }
+                    if let Some(stroke) = &mut shape.stroke {
+                        stroke.paint = stroke.paint.map_colors(transform);
+                    }
+                }
+                FrameItem::Image(image, _, _) => {
+                    if let Some(mapped) = image.map_colors(transform) {
+                        *image = mapped;
+                    }
+                }
+                FrameItem::Link(_, _) | FrameItem::Tag(_) => {}
+            }
+        }
+    }
+
+    /// Fail if the pixels of a raster image in the frame (including those in
+    /// patterns) could not be decoded.
+    ///
+    /// Raster images are decoded lazily, so this blocks until all of them are
+    /// decoded. It is meant for exporters, which need the pixels anyway.
+    pub fn check_images(&self) -> SourceResult<()> {
+        let check_paint = |paint: &Paint| match paint {
+            Paint::Pattern(pattern) => pattern.frame().check_images(),
+            Paint::Solid(_) | Paint::Gradient(_) => Ok(()),
+        };
+
+        for (_, item) in self.items() {
+            match item {
+                FrameItem::Group(group) => group.frame.check_images()?,
+                FrameItem::Text(text) => {
+                    check_paint(&text.fill)?;
+                    if let Some(stroke) = &text.stroke {
+                        check_paint(&stroke.paint)?;
+                    }
+                }
+                FrameItem::Shape(shape, _) => {
+                    if let Some(fill) = &shape.fill {
+                        check_paint(fill)?;
+                    }
+                    if let Some(stroke) = &shape.stroke {
+                        check_paint(&stroke.paint)?;
+                    }
+                }
+                FrameItem::Image(image, _, span) => {
+                    if let ImageKind::Raster(raster) = image.kind() {
+                        raster.decoded().at(*span)?;
+                    }
+                }
+                FrameItem::Link(_, _) | FrameItem::Tag(_) => {}
+            }
+        }
+        Ok(())
+    }
+
+    /// Replace the contents of all placeholder boxes with the given name by
+    /// the `replacement` frame. Returns how many boxes were filled.
+    ///
+    /// Fails if the replacement does not fit into one of the reserved boxes.
+    pub fn fill_placeholder(
+        &mut self,
+        name: &str,
+        replacement: &Frame,
+    ) -> StrResult<usize> {
+        // Don't clone items of subframes that don't contain the placeholder.
+        if !self.placeholders().iter().any(|(other, _)| other == name) {
+            return Ok(0);
+        }
+
+        let mut count = 0;
+        let mut pending = false;
+        for (_, item) in Arc::make_mut(&mut self.items).iter_mut() {
+            match item {
+                FrameItem::Tag(elem) => {
+                    pending = elem
+                        .to_packed::<PlaceholderElem>()
+                        .is_some_and(|elem| elem.name() == name);
+                    continue;
+                }
+                FrameItem::Group(group) if pending => {
+                    let reserved = group.frame.size();
+                    if !reserved.fits(replacement.size()) {
+                        bail!(
+                            "replacement for placeholder `{name}` does not fit \
+                             into its reserved box"
+                        );
+                    }
+                    let mut frame = Frame::hard(reserved);
+                    if group.frame.has_baseline() {
+                        frame.set_baseline(group.frame.baseline());
+                    }
+                    frame.push_frame(Point::zero(), replacement.clone());
+                    group.frame = frame;
+                    count += 1;
+                }
+                FrameItem::Group(group) => {
+                    count += group.frame.fill_placeholder(name, replacement)?;
+                }
+                _ => {}
+            }
+            pending = false;
+        }
+
+        Ok(count)
+    }
+
     /// Wrap the frame's contents in a group and modify that group with `f`.
     fn group<F>(&mut self, f: F)
     where
@@ -512,6 +777,20 @@ pub enum FrameItem {
     Tag(Content),
 }
 
+impl FrameItem {
+    /// Computes the bounding box of the item's visible contents relative to
+    /// its position, like [`Frame::bounding_box`] does for a whole frame.
+    pub fn bounding_box(&self) -> Option<(Point, Point)> {
+        match self {
+            Self::Group(group) => group.outer_bounding_box(group.frame.bounding_box()?),
+            Self::Text(text) => text_bounding_box(text),
+            Self::Shape(shape, _) => shape_bounding_box(shape),
+            Self::Image(_, size, _) => Some((Point::zero(), size.to_point())),
+            Self::Link(_, _) | Self::Tag(_) => None,
+        }
+    }
+}
+
 impl Debug for FrameItem {
     fn fmt(&self, f: &mut Formatter) -> fmt::Result {
         match self {
@@ -545,6 +824,27 @@ impl GroupItem {
             clip_path: None,
         }
     }
+
+    /// Maps a bounding box of the group's frame contents into the coordinates
+    /// of the parent frame, applying the group's clipping and transformation.
+    ///
+    /// Returns `None` if the clip path hides the whole box.
+    pub fn outer_bounding_box(&self, inner: (Point, Point)) -> Option<(Point, Point)> {
+        let (mut min, mut max) = inner;
+
+        if let Some(clip_path) = &self.clip_path {
+            let (clip_min, clip_max) = path_bounding_box(clip_path)?;
+            min = min.max(clip_min);
+            max = max.min(clip_max);
+            if min.x > max.x || min.y > max.y {
+                return None;
+            }
+        }
+
+        let corners = [min, Point::new(max.x, min.y), Point::new(min.x, max.y), max]
+            .map(|corner| corner.transform(self.transform));
+        corners.into_iter().map(|p| (p, p)).reduce(|a, b| union(Some(a), b))
+    }
 }
 
 impl Debug for GroupItem {
@@ -584,3 +884,120 @@ impl From<Position> for Dict {
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::foundations::NativeElement;
+    use crate::layout::Angle;
+
+    fn pt(x: f64, y: f64) -> Point {
+        Point::new(Abs::pt(x), Abs::pt(y))
+    }
+
+    #[test]
+    fn test_bounding_box_of_stroked_shape() {
+        let mut frame = Frame::soft(Size::splat(Abs::pt(100.0)));
+        let stroke = FixedStroke::from_pair(Color::BLACK, Abs::pt(2.0));
+        let shape = Geometry::Rect(Size::splat(Abs::pt(10.0))).stroked(stroke);
+        frame.push(pt(20.0, 20.0), FrameItem::Shape(shape, Span::detached()));
+        assert_eq!(frame.bounding_box(), Some((pt(19.0, 19.0), pt(31.0, 31.0))));
+    }
+
+    #[test]
+    fn test_bounding_box_of_rotated_group() {
+        let mut inner = Frame::soft(Size::splat(Abs::pt(10.0)));
+        let line = Geometry::Line(pt(10.0, 0.0))
+            .stroked(FixedStroke::from_pair(Color::BLACK, Abs::zero()));
+        inner.push(Point::zero(), FrameItem::Shape(line, Span::detached()));
+        inner.transform(Transform::rotate(Angle::deg(90.0)));
+
+        let (min, max) = inner.bounding_box().unwrap();
+        assert!(min.x.approx_eq(Abs::zero()) && min.y.approx_eq(Abs::zero()));
+        assert!(max.x.approx_eq(Abs::zero()) && max.y.approx_eq(Abs::pt(10.0)));
+    }
+
+    #[test]
+    fn test_bounding_box_of_empty_frame() {
+        let frame = Frame::soft(Size::splat(Abs::pt(10.0)));
+        assert_eq!(frame.bounding_box(), None);
+    }
+
+    /// Reserves a box of the given size for the placeholder with the name.
+    fn reserve(frame: &mut Frame, pos: Point, name: &str, size: Size) {
+        let elem = PlaceholderElem::new(name.into()).pack();
+        frame.push(pos, FrameItem::Tag(elem));
+        frame.push(pos, FrameItem::Group(GroupItem::new(Frame::hard(size))));
+    }
+
+    /// The frames of all groups in the frame, recursively.
+    fn groups(frame: &Frame) -> Vec<&Frame> {
+        let mut found = vec![];
+        for (_, item) in frame.items() {
+            if let FrameItem::Group(group) = item {
+                found.push(&group.frame);
+                found.extend(groups(&group.frame));
+            }
+        }
+        found
+    }
+
+    #[test]
+    fn test_fill_placeholder() {
+        let reserved = Size::new(Abs::pt(20.0), Abs::pt(10.0));
+        let mut nested = Frame::hard(Size::splat(Abs::pt(50.0)));
+        reserve(&mut nested, pt(5.0, 5.0), "serial", reserved);
+        let mut frame = Frame::hard(Size::splat(Abs::pt(100.0)));
+        reserve(&mut frame, pt(0.0, 0.0), "serial", reserved);
+        reserve(&mut frame, pt(0.0, 20.0), "name", reserved);
+        frame.push(pt(0.0, 40.0), FrameItem::Group(GroupItem::new(nested)));
+
+        let mut replacement = Frame::soft(Size::new(Abs::pt(15.0), Abs::pt(5.0)));
+        let shape = Geometry::Rect(replacement.size()).filled(Color::BLACK.into());
+        replacement
+            .push(Point::zero(), FrameItem::Shape(shape.clone(), Span::detached()));
+
+        assert_eq!(frame.fill_placeholder("serial", &replacement), Ok(2));
+        assert_eq!(frame.fill_placeholder("missing", &replacement), Ok(0));
+
+        // The filled boxes keep their size and contain the replacement, while
+        // the other placeholder stays empty.
+        let boxes: Vec<_> = groups(&frame)
+            .into_iter()
+            .filter(|group| group.size() == reserved)
+            .map(|group| {
+                group
+                    .items()
+                    .map(|(pos, item)| match item {
+                        FrameItem::Shape(shape, _) => (*pos, shape.clone()),
+                        _ => panic!("unexpected item"),
+                    })
+                    .collect::<Vec<_>>()
+            })
+            .collect();
+        let filled = vec![(Point::zero(), shape)];
+        assert_eq!(boxes, [filled.clone(), vec![], filled]);
+        assert_eq!(
+            frame.placeholders(),
+            [
+                ("serial".into(), reserved),
+                ("name".into(), reserved),
+                ("serial".into(), reserved),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_fill_placeholder_too_large() {
+        let mut frame = Frame::hard(Size::splat(Abs::pt(100.0)));
+        reserve(&mut frame, Point::zero(), "serial", Size::splat(Abs::pt(10.0)));
+        let replacement = Frame::soft(Size::new(Abs::pt(15.0), Abs::pt(5.0)));
+        assert_eq!(
+            frame.fill_placeholder("serial", &replacement),
+            Err(
+                "replacement for placeholder `serial` does not fit into its reserved box"
+                    .into()
+            )
+        );
+    }
+}
@@ -14,6 +14,23 @@ use crate::foundations::{elem, Content, Packed, Show, StyleChain};
 /// Hello Jane \
 /// #hide[Hello] Joe
 /// ```
+///
+/// # Semantics
+/// Hidden content is laid out, but nothing of it is painted: Its text can't
+/// be selected or extracted from the exported PDF and links within it are
+/// not clickable. However, it remains visible to
+/// [introspection]($category/introspection): Headings, figures, and labels
+/// inside of it can still be [queried]($query), step [counters]($counter),
+/// and be referenced.
+///
+/// If you want content to neither take up space nor be introspectable, don't
+/// include it at all, e.g. by wrapping it in an `{if}` expression.
+///
+/// ```example
+/// #let show-solutions = false
+/// Question: What is $1 + 1$?
+/// #if show-solutions [Answer: $2$]
+/// ```
 #[elem(Show)]
 pub struct HideElem {
     /// The content to hide.
@@ -5,10 +5,12 @@ use comemo::{Tracked, TrackedMut};
 use unicode_bidi::{BidiInfo, Level as BidiLevel};
 use unicode_script::{Script, UnicodeScript};
 
+pub use self::shaping::{shape_text, RunGlyph, ShapedRun};
+
 use self::linebreak::{breakpoints, Breakpoint};
 use self::shaping::{
-    cjk_punct_style, is_of_cj_script, shape, ShapedGlyph, ShapedText, BEGIN_PUNCT_PAT,
-    END_PUNCT_PAT,
+    cjk_punct_style, is_of_cj_script, is_shaping_compatible, shape, ShapedGlyph,
+    ShapedText, BEGIN_PUNCT_PAT, END_PUNCT_PAT,
 };
 use crate::diag::{bail, SourceResult};
 use crate::engine::{Engine, Route};
@@ -23,7 +25,8 @@ use crate::math::{EquationElem, MathParItem};
 use crate::model::{Linebreaks, ParElem};
 use crate::syntax::Span;
 use crate::text::{
-    Lang, LinebreakElem, SmartQuoteElem, SmartQuoter, SmartQuotes, SpaceElem, TextElem,
+    GlyphElem, Lang, LinebreakElem, SmartQuoteElem, SmartQuoter, SmartQuotes, SpaceElem,
+    TextElem,
 };
 use crate::utils::Numeric;
 use crate::World;
@@ -201,6 +204,8 @@ enum Segment<'a> {
     Equation(Vec<MathParItem>),
     /// A box with arbitrary content.
     Box(&'a Packed<BoxElem>, bool),
+    /// A glyph selected by its index.
+    Glyph(&'a Packed<GlyphElem>),
     /// A tag.
     Tag(&'a Packed<TagElem>),
 }
@@ -214,6 +219,7 @@ impl Segment<'_> {
             Self::Box(_, frac) => {
                 (if frac { SPACING_REPLACE } else { OBJ_REPLACE }).len_utf8()
             }
+            Self::Glyph(_) => OBJ_REPLACE.len_utf8(),
             Self::Equation(ref par_items) => par_items
                 .iter()
                 .map(MathParItem::text)
@@ -561,6 +567,9 @@ fn collect<'a>(
             let frac = elem.width(styles).is_fractional();
             full.push(if frac { SPACING_REPLACE } else { OBJ_REPLACE });
             Segment::Box(elem, frac)
+        } else if let Some(elem) = child.to_packed::<GlyphElem>() {
+            full.push(OBJ_REPLACE);
+            Segment::Glyph(elem)
         } else if let Some(elem) = child.to_packed::<TagElem>() {
             Segment::Tag(elem)
         } else {
@@ -610,13 +619,28 @@ fn prepare<'a>(
 
     let mut cursor = 0;
     let mut items = Vec::with_capacity(segments.len());
+    let across_styles = ParElem::shape_across_styles_in(styles);
 
     // Shape / layout the children and collect them into items.
-    for (segment, styles) in segments {
-        let end = cursor + segment.len();
+    let mut segments = segments.into_iter().peekable();
+    while let Some((segment, styles)) = segments.next() {
+        let mut end = cursor + segment.len();
         match segment {
             Segment::Text(_) => {
-                shape_range(&mut items, engine, &bidi, cursor..end, &spans, styles);
+                // If enabled, shape adjacent text whose styles only differ in
+                // properties that don't affect shaping (e.g. the fill)
+                // together, so that partial styling keeps kerning and
+                // ligatures intact.
+                let mut runs = vec![(cursor..end, styles)];
+                while let Some(&(Segment::Text(len), next)) = segments.peek() {
+                    if !across_styles || !is_shaping_compatible(styles, next) {
+                        break;
+                    }
+                    runs.push((end..end + len, next));
+                    end += len;
+                    segments.next();
+                }
+                shape_range(&mut items, engine, &bidi, cursor..end, &spans, &runs);
             }
             Segment::Spacing(spacing) => match spacing {
                 Spacing::Rel(v) => {
@@ -651,6 +675,12 @@ fn prepare<'a>(
                     items.push(Item::Frame(frame));
                 }
             }
+            Segment::Glyph(elem) => {
+                let mut frame = elem.layout(engine, styles)?;
+                frame.post_process(styles);
+                frame.translate(Point::with_y(TextElem::baseline_in(styles)));
+                items.push(Item::Frame(frame));
+            }
             Segment::Tag(tag) => {
                 items.push(Item::Tag(tag));
             }
@@ -732,30 +762,62 @@ fn add_cjk_latin_spacing(items: &mut [Item]) {
 
 /// Group a range of text by BiDi level and script, shape the runs and generate
 /// items for them.
+///
+/// The range is covered by one or multiple style `runs`, which must all be
+/// [shaping compatible](is_shaping_compatible). The text is shaped with the
+/// first run's styles and then split up into one item per run.
 fn shape_range<'a>(
     items: &mut Vec<Item<'a>>,
     engine: &Engine,
     bidi: &BidiInfo<'a>,
     range: Range,
     spans: &SpanMapper,
-    styles: StyleChain<'a>,
+    runs: &[(Range, StyleChain<'a>)],
 ) {
+    let styles = runs[0].1;
     let script = TextElem::script_in(styles);
     let lang = TextElem::lang_in(styles);
     let region = TextElem::region_in(styles);
+    let ligatures = TextElem::ligatures_in(styles);
     let mut process = |range: Range, level: BidiLevel| {
         let dir = if level.is_ltr() { Dir::LTR } else { Dir::RTL };
         let shaped = shape(
-            engine,
+            engine.world,
             range.start,
-            &bidi.text[range],
+            &bidi.text[range.clone()],
             spans,
             styles,
             dir,
             lang,
             region,
         );
-        items.push(Item::Text(shaped));
+
+        if runs.len() == 1 {
+            items.push(Item::Text(shaped));
+        } else if ligatures.is_auto()
+            && runs.iter().skip(1).any(|(run, _)| shaped.crosses(run.start))
+        {
+            // A ligature was formed across a style boundary. To suppress it,
+            // we shape the runs separately instead.
+            for (run, styles) in runs {
+                let run = run.start.max(range.start)..run.end.min(range.end);
+                if run.is_empty() {
+                    continue;
+                }
+                items.push(Item::Text(shape(
+                    engine.world,
+                    run.start,
+                    &bidi.text[run],
+                    spans,
+                    *styles,
+                    dir,
+                    lang,
+                    region,
+                )));
+            }
+        } else {
+            items.extend(shaped.split(runs).into_iter().map(Item::Text));
+        }
     };
 
     let mut prev_level = BidiLevel::ltr();
@@ -5,6 +5,7 @@ use std::str::FromStr;
 use std::sync::Arc;
 
 use az::SaturatingAs;
+use comemo::{Track, Tracked};
 use ecow::EcoString;
 use rustybuzz::{ShapePlan, Tag, UnicodeBuffer};
 use unicode_script::{Script, UnicodeScript};
@@ -433,7 +434,7 @@ impl<'a> ShapedText<'a> {
             }
         } else {
             shape(
-                engine,
+                engine.world,
                 text_range.start,
                 text,
                 spans,
@@ -445,6 +446,66 @@ impl<'a> ShapedText<'a> {
         }
     }
 
+    /// Whether a glyph cluster crosses the given text index, i.e. whether the
+    /// text can't be divided there without reshaping.
+    ///
+    /// The text index is relative to the whole paragraph.
+    pub fn crosses(&self, text_index: usize) -> bool {
+        self.glyphs
+            .iter()
+            .any(|g| g.range.start < text_index && text_index < g.range.end)
+    }
+
+    /// Split the shaped text into one part per style run, keeping the glyph
+    /// positions from shaping the text as a whole.
+    ///
+    /// The runs' text ranges are relative to the whole paragraph. A cluster
+    /// that crosses a run boundary (e.g. a ligature) can't be divided, so the
+    /// runs on both sides of it are merged and take the styles of the first.
+    pub fn split(self, runs: &[(Range<usize>, StyleChain<'a>)]) -> Vec<ShapedText<'a>> {
+        let end = self.base + self.text.len();
+        let mut parts: Vec<(Range<usize>, StyleChain<'a>)> = vec![];
+        for (range, styles) in runs {
+            let range = range.start.max(self.base)..range.end.min(end);
+            if range.is_empty() {
+                continue;
+            }
+
+            match parts.last_mut() {
+                Some((prev, _)) if self.crosses(range.start) => prev.end = range.end,
+                _ => parts.push((range, *styles)),
+            }
+        }
+
+        if parts.is_empty() {
+            return vec![self];
+        }
+
+        parts
+            .into_iter()
+            .map(|(range, styles)| {
+                let glyphs: Vec<ShapedGlyph> = self
+                    .glyphs
+                    .iter()
+                    .filter(|g| range.contains(&g.range.start))
+                    .cloned()
+                    .collect();
+                Self {
+                    base: range.start,
+                    text: &self.text[range.start - self.base..range.end - self.base],
+                    dir: self.dir,
+                    lang: self.lang,
+                    region: self.region,
+                    styles,
+                    variant: self.variant,
+                    size: self.size,
+                    width: glyphs.iter().map(|g| g.x_advance).sum::<Em>().at(self.size),
+                    glyphs: Cow::Owned(glyphs),
+                }
+            })
+            .collect()
+    }
+
     /// Push a hyphen to end of the text.
     pub fn push_hyphen(&mut self, engine: &Engine, fallback: bool) {
         self.insert_hyphen(engine, fallback, Side::Right)
@@ -590,7 +651,7 @@ impl Debug for ShapedText<'_> {
 
 /// Holds shaping results and metadata common to all shaped segments.
 struct ShapingContext<'a, 'v> {
-    engine: &'a Engine<'v>,
+    world: Tracked<'a, dyn World + 'v>,
     spans: &'a SpanMapper,
     glyphs: Vec<ShapedGlyph>,
     used: Vec<Font>,
@@ -605,7 +666,7 @@ struct ShapingContext<'a, 'v> {
 /// Shape text into [`ShapedText`].
 #[allow(clippy::too_many_arguments)]
 pub(super) fn shape<'a>(
-    engine: &Engine,
+    world: Tracked<dyn World + '_>,
     base: usize,
     text: &'a str,
     spans: &SpanMapper,
@@ -616,7 +677,7 @@ pub(super) fn shape<'a>(
 ) -> ShapedText<'a> {
     let size = TextElem::size_in(styles);
     let mut ctx = ShapingContext {
-        engine,
+        world,
         spans,
         size,
         glyphs: vec![],
@@ -654,6 +715,87 @@ pub(super) fn shape<'a>(
     }
 }
 
+/// Shape a string of text in the same way as it would be shaped in a
+/// paragraph with the given styles.
+///
+/// This applies font selection and fallback, features, tracking, and spacing.
+/// It does not perform line breaking, bidirectional reordering, or
+/// justification, so the result corresponds to a single unbroken run of text
+/// in the styles' text direction. This is useful for external tools that need
+/// to measure text exactly like Typst.
+pub fn shape_text(world: &dyn World, text: &str, styles: StyleChain) -> ShapedRun {
+    let world = world.track();
+    let dir = TextElem::dir_in(styles);
+    let lang = TextElem::lang_in(styles);
+    let region = TextElem::region_in(styles);
+    let shaped = shape(world, 0, text, &SpanMapper::new(), styles, dir, lang, region);
+    let size = shaped.size;
+    ShapedRun {
+        size,
+        width: shaped.width,
+        glyphs: shaped
+            .glyphs
+            .iter()
+            .map(|glyph| RunGlyph {
+                font: glyph.font.clone(),
+                id: glyph.glyph_id,
+                x_advance: glyph.x_advance.at(size),
+                x_offset: glyph.x_offset.at(size),
+                y_offset: glyph.y_offset.at(size),
+                range: glyph.range.clone(),
+            })
+            .collect(),
+    }
+}
+
+/// The result of [`shape_text`].
+#[derive(Debug, Clone)]
+pub struct ShapedRun {
+    /// The font size the text was shaped at.
+    pub size: Abs,
+    /// The total advance width of the text.
+    pub width: Abs,
+    /// The shaped glyphs in visual order.
+    pub glyphs: Vec<RunGlyph>,
+}
+
+/// A single glyph in a [`ShapedRun`].
+#[derive(Debug, Clone)]
+pub struct RunGlyph {
+    /// The font the glyph is contained in.
+    pub font: Font,
+    /// The glyph's index in the font.
+    pub id: u16,
+    /// The advance width of the glyph.
+    pub x_advance: Abs,
+    /// The horizontal offset of the glyph.
+    pub x_offset: Abs,
+    /// The vertical offset of the glyph.
+    pub y_offset: Abs,
+    /// The byte range of the glyph's cluster in the shaped text.
+    ///
+    /// Multiple glyphs can belong to the same cluster, e.g. for combining
+    /// marks, and a cluster can span multiple characters, e.g. for ligatures.
+    pub range: Range<usize>,
+}
+
+/// Whether text with these two styles produces the same glyphs and positions
+/// when shaped, i.e. whether they only differ in properties that are applied
+/// after shaping (like the fill or decorations).
+pub(super) fn is_shaping_compatible(a: StyleChain, b: StyleChain) -> bool {
+    TextElem::size_in(a) == TextElem::size_in(b)
+        && TextElem::tracking_in(a) == TextElem::tracking_in(b)
+        && TextElem::spacing_in(a) == TextElem::spacing_in(b)
+        && TextElem::lang_in(a) == TextElem::lang_in(b)
+        && TextElem::region_in(a) == TextElem::region_in(b)
+        && TextElem::script_in(a) == TextElem::script_in(b)
+        && TextElem::dir_in(a) == TextElem::dir_in(b)
+        && TextElem::fallback_in(a) == TextElem::fallback_in(b)
+        && variant(a) == variant(b)
+        && families(a).eq(families(b))
+        && features(a) == features(b)
+}
+
 /// Shape text with font fallback using the `families` iterator.
 fn shape_segment<'a>(
     ctx: &mut ShapingContext,
@@ -667,7 +809,7 @@ fn shape_segment<'a>(
     }
 
     // Find the next available family.
-    let world = ctx.engine.world;
+    let world = ctx.world;
     let book = world.book();
     let mut selection = families.find_map(|family| {
         book.select(family, ctx.variant)
@@ -1104,3 +1246,54 @@ fn is_justifiable(
         || is_cjk_right_aligned_punctuation(c, x_advance, stretchability)
         || is_cjk_center_aligned_punctuation(c, style)
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::foundations::Bytes;
+    use crate::memory::MemoryWorld;
+
+    fn world() -> MemoryWorld {
+        let mut world = MemoryWorld::new("");
+        for data in typst_dev_assets::fonts() {
+            world.add_fonts(Bytes::from_static(data));
+        }
+        world
+    }
+
+    #[test]
+    fn test_shape_text() {
+        let world = world();
+        let styles = StyleChain::new(&world.library().styles);
+        let run = shape_text(&world, "III", styles);
+        assert_eq!(run.size, Abs::pt(11.0));
+        assert_eq!(run.glyphs.len(), 3);
+
+        let font = &run.glyphs[0].font;
+        assert_eq!(font.info().family, "Linux Libertine");
+        let id = font.ttf().glyph_index('I').unwrap().0;
+        let advance = font.advance(id).unwrap().at(run.size);
+        assert!(run.width.approx_eq(3.0 * advance));
+        for (i, glyph) in run.glyphs.iter().enumerate() {
+            assert_eq!(glyph.id, id);
+            assert_eq!(glyph.range, i..i + 1);
+        }
+    }
+
+    #[test]
+    fn test_shape_text_fallback() {
+        let world = world();
+        let styles = StyleChain::new(&world.library().styles);
+        let run = shape_text(&world, "A─", styles);
+        assert_eq!(run.glyphs.len(), 2);
+
+        // Linux Libertine has no box drawing characters, so the second glyph
+        // comes from a fallback font that has one.
+        let (first, second) = (&run.glyphs[0], &run.glyphs[1]);
+        assert_eq!(first.font.info().family, "Linux Libertine");
+        assert_eq!(first.font.ttf().glyph_index('─'), None);
+        assert_ne!(second.font, first.font);
+        assert_eq!(second.font.ttf().glyph_index('─').map(|id| id.0), Some(second.id));
+        assert_eq!(second.range, 1..4);
+    }
+}
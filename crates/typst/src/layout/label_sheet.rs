@@ -0,0 +1,183 @@
+use std::num::NonZeroUsize;
+
+use comemo::Track;
+
+use crate::diag::{bail, SourceResult};
+use crate::engine::Engine;
+use crate::foundations::{elem, Context, Func, Packed, Smart, StyleChain};
+use crate::introspection::Locatable;
+use crate::layout::{
+    Abs, Axes, Fragment, Frame, LayoutMultiple, Length, Point, Regions, Size,
+};
+use crate::utils::{NonZeroExt, Numeric};
+
+/// Repeats content on a sheet of labels or tickets.
+///
+/// The labels are arranged in a grid of `rows` and `columns` per sheet, with
+/// each sheet filling one page. The content of each label is produced by a
+/// function that receives the label's zero-based index. This makes it easy to
+/// print addresses, name badges or numbered tickets on pre-cut sheets.
+///
+/// To match the physical layout of a sheet, set the page's size and margins
+/// to the sheet's dimensions and its top and left margins. The labels are
+/// positioned relative to the top-left corner of the region they are placed
+/// in, so they should come first on their page.
+///
+/// # Example
+/// ```example
+/// #set page(width: 120pt, height: 80pt, margin: 10pt)
+/// #label-sheet(
+///   columns: 2,
+///   rows: 3,
+///   gutter: 4pt,
+///   i => rect(
+///     width: 100%,
+///     height: 100%,
+///     inset: 2pt,
+///   )[Ticket #(i + 1)],
+/// )
+/// ```
+#[elem(Locatable, LayoutMultiple)]
+pub struct LabelSheetElem {
+    /// The number of labels next to each other on a sheet.
+    #[default(NonZeroUsize::ONE)]
+    pub columns: NonZeroUsize,
+
+    /// The number of labels below each other on a sheet.
+    #[default(NonZeroUsize::ONE)]
+    pub rows: NonZeroUsize,
+
+    /// The width of each label.
+    ///
+    /// If this is `{auto}`, the available width is split evenly between the
+    /// columns.
+    #[resolve]
+    pub width: Smart<Length>,
+
+    /// The height of each label.
+    ///
+    /// If this is `{auto}`, the available height is split evenly between the
+    /// rows.
+    #[resolve]
+    pub height: Smart<Length>,
+
+    /// The gap between adjacent labels.
+    #[resolve]
+    pub gutter: Length,
+
+    /// A horizontal offset applied to all labels.
+    ///
+    /// Printers often shift their output slightly. Use this and `dy` to
+    /// calibrate the labels to a physical sheet.
+    #[resolve]
+    pub dx: Length,
+
+    /// A vertical offset applied to all labels.
+    #[resolve]
+    pub dy: Length,
+
+    /// How many labels to produce.
+    ///
+    /// If this is `{auto}`, the rest of the current sheet is filled.
+    pub count: Smart<usize>,
+
+    /// How many label slots to leave empty at the start.
+    ///
+    /// This allows reusing sheets from which some labels were already
+    /// peeled off. Skipped slots don't count towards the label indices.
+    #[default(0)]
+    pub skip: usize,
+
+    /// A function that receives the index of a label and returns its content.
+    #[required]
+    pub content: Func,
+}
+
+impl LayoutMultiple for Packed<LabelSheetElem> {
+    #[typst_macros::time(name = "label-sheet", span = self.span())]
+    fn layout(
+        &self,
+        engine: &mut Engine,
+        styles: StyleChain,
+        mut regions: Regions,
+    ) -> SourceResult<Fragment> {
+        let columns = self.columns(styles).get();
+        let rows = self.rows(styles).get();
+        let gutter = self.gutter(styles);
+        let offset = Point::new(self.dx(styles), self.dy(styles));
+
+        let per_sheet = columns * rows;
+        let skip = self.skip(styles);
+        let count = self.count(styles).unwrap_or(per_sheet - skip % per_sheet);
+        let end = skip + count;
+
+        let loc = self.location().unwrap();
+        let context = Context::new(Some(loc), Some(styles));
+        let mut frames = vec![];
+        let mut index = skip;
+
+        while index < end {
+            // Determine the size of each label on this sheet. Labels are split
+            // from the full region rather than the space that remains in it.
+            let base = regions.base();
+            let split = |available: Abs, n: usize| {
+                (available - gutter * (n - 1) as f64) / n as f64
+            };
+            let width = match self.width(styles) {
+                Smart::Custom(width) => width,
+                Smart::Auto if base.x.is_finite() => split(base.x, columns),
+                Smart::Auto => {
+                    bail!(
+                        self.span(),
+                        "cannot divide an infinite width into label columns"
+                    )
+                }
+            };
+            let height = match self.height(styles) {
+                Smart::Custom(height) => height,
+                Smart::Auto if base.y.is_finite() => split(base.y, rows),
+                Smart::Auto => {
+                    bail!(self.span(), "cannot divide an infinite height into label rows")
+                }
+            };
+
+            // A positive offset pushes the labels further out, so it is part
+            // of the sheet's size.
+            let grid = Size::new(
+                width * columns as f64
+                    + gutter * (columns - 1) as f64
+                    + offset.x.max(Abs::zero()),
+                height * rows as f64
+                    + gutter * (rows - 1) as f64
+                    + offset.y.max(Abs::zero()),
+            );
+            let mut output = Frame::hard(regions.expand.select(regions.size, grid));
+
+            // Lay out the labels that belong to this sheet.
+            let sheet = index / per_sheet;
+            while index < end && index / per_sheet == sheet {
+                let slot = index % per_sheet;
+                let (row, column) = (slot / columns, slot % columns);
+                let pos = offset
+                    + Point::new(
+                        (width + gutter) * column as f64,
+                        (height + gutter) * row as f64,
+                    );
+
+                let content = self
+                    .content()
+                    .call(engine, context.track(), [index - skip])?
+                    .display();
+                let pod = Regions::one(Size::new(width, height), Axes::splat(true));
+                let frame = content.layout(engine, styles, pod)?.into_frame();
+                output.push_frame(pos, frame);
+                index += 1;
+            }
+
+            frames.push(output);
+            regions.next();
+        }
+
+        Ok(Fragment::frames(frames))
+    }
+}
@@ -2,8 +2,10 @@ use comemo::Tracked;
 
 use crate::diag::{At, SourceResult};
 use crate::engine::Engine;
-use crate::foundations::{dict, func, Content, Context, Dict, StyleChain, Styles};
-use crate::layout::{Abs, Axes, LayoutMultiple, Regions, Size};
+use crate::foundations::{
+    dict, func, Content, Context, Dict, Resolve, Smart, StyleChain, Styles,
+};
+use crate::layout::{Abs, Axes, Frame, FrameItem, LayoutMultiple, Length, Regions, Size};
 use crate::syntax::Span;
 
 /// Measures the layouted size of content.
@@ -38,8 +40,27 @@ use crate::syntax::Span;
 /// #thing[Welcome]
 /// ```
 ///
-/// The measure function returns a dictionary with the entries `width` and
-/// `height`, both of type [`length`].
+/// By default, the content is measured in an infinite space. To find out how
+/// it behaves in a constrained space, for example how high a paragraph becomes
+/// when it must fit into a given width, you can pass a `width` and/or `height`.
+///
+/// ```example
+/// #context measure(
+///   width: 3cm,
+///   lorem(12),
+/// ).height
+/// ```
+///
+/// The measure function returns a dictionary with the following entries:
+/// - `width` and `height`: The size of the content, both of type [`length`].
+/// - `baseline`: The distance from the top of the content to its baseline,
+///   which is the baseline of its first line of text. For content without
+///   any text, this is the same as its height.
+/// - `ascent` and `descent`: The distances from the baseline to the top and
+///   bottom of the content.
+/// - `breaks`: Whether the content would break across multiple regions because
+///   it doesn't fit into the given `height`. If it does, the other entries
+///   only describe the part in the first region.
 #[func(contextual)]
 pub fn measure(
     /// The engine.
@@ -48,6 +69,19 @@ pub fn measure(
     context: Tracked<Context>,
     /// The callsite span.
     span: Span,
+    /// The width available to the content.
+    ///
+    /// If this is `{auto}`, the available width is infinite.
+    #[named]
+    #[default(Smart::Auto)]
+    width: Smart<Length>,
+    /// The height available to the content.
+    ///
+    /// If this is `{auto}`, the available height is infinite and the content
+    /// never breaks.
+    #[named]
+    #[default(Smart::Auto)]
+    height: Smart<Length>,
     /// The content whose size to measure.
     content: Content,
     /// _Compatibility:_ This argument only exists for compatibility with
@@ -60,8 +94,41 @@ pub fn measure(
         None => context.styles().at(span)?,
     };
 
-    let pod = Regions::one(Axes::splat(Abs::inf()), Axes::splat(false));
-    let frame = content.measure(engine, styles, pod)?.into_frame();
+    let available = Size::new(
+        width.resolve(styles).unwrap_or(Abs::inf()),
+        height.resolve(styles).unwrap_or(Abs::inf()),
+    );
+    let pod = Regions::repeat(available, Axes::splat(false));
+    let fragment = content.measure(engine, styles, pod)?;
+    let breaks = fragment.len() > 1;
+    let frame = fragment.into_frames().swap_remove(0);
     let Size { x, y } = frame.size();
-    Ok(dict! { "width" => x, "height" => y })
+    let baseline = find_baseline(&frame).unwrap_or(y);
+    Ok(dict! {
+        "width" => x,
+        "height" => y,
+        "baseline" => baseline,
+        "ascent" => baseline,
+        "descent" => y - baseline,
+        "breaks" => breaks,
+    })
+}
+
+/// Find the baseline of a measured frame.
+///
+/// Frames from flow layout don't have a baseline of their own, so this falls
+/// back to the baseline of the first text in the frame, which lies in its
+/// first line.
+fn find_baseline(frame: &Frame) -> Option<Abs> {
+    if frame.has_baseline() {
+        return Some(frame.baseline());
+    }
+
+    frame.items().find_map(|(pos, item)| match item {
+        FrameItem::Text(_) => Some(pos.y),
+        FrameItem::Group(group) if group.transform.is_identity() => {
+            find_baseline(&group.frame).map(|baseline| pos.y + baseline)
+        }
+        _ => None,
+    })
 }
@@ -16,6 +16,7 @@ mod frame;
 mod grid;
 mod hide;
 mod inline;
+mod label_sheet;
 #[path = "layout.rs"]
 mod layout_;
 mod length;
@@ -50,6 +51,7 @@ pub use self::fragment::*;
 pub use self::frame::*;
 pub use self::grid::*;
 pub use self::hide::*;
+pub use self::label_sheet::*;
 pub use self::layout_::*;
 pub use self::length::*;
 pub use self::measure_::*;
@@ -68,6 +70,7 @@ pub use self::stack::*;
 pub use self::transform::*;
 
 pub(crate) use self::inline::*;
+pub use self::inline::{shape_text, RunGlyph, ShapedRun};
 
 use comemo::{Tracked, TrackedMut};
 
@@ -106,6 +109,7 @@ pub fn define(global: &mut Scope) {
     global.define_elem::<GridElem>();
     global.define_elem::<ColumnsElem>();
     global.define_elem::<ColbreakElem>();
+    global.define_elem::<LabelSheetElem>();
     global.define_elem::<PlaceElem>();
     global.define_elem::<AlignElem>();
     global.define_elem::<PadElem>();
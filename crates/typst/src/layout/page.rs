@@ -6,23 +6,24 @@ use std::str::FromStr;
 
 use comemo::Track;
 
-use crate::diag::{bail, SourceResult};
+use crate::diag::{bail, warning, SourceResult};
 use crate::engine::Engine;
 use crate::foundations::{
-    cast, elem, AutoValue, Cast, Content, Context, Dict, Fold, Func, NativeElement,
-    Packed, Resolve, Smart, StyleChain, Value,
+    cast, elem, AutoValue, Cast, Content, Context, Dict, Duration, Fold, Func,
+    NativeElement, Packed, Resolve, Smart, StyleChain, Value,
 };
 use crate::introspection::{Counter, CounterDisplayElem, CounterKey, ManualPageCounter};
 use crate::layout::{
-    Abs, AlignElem, Alignment, Axes, ColumnsElem, Dir, Frame, HAlignment, LayoutMultiple,
-    Length, OuterVAlignment, Point, Ratio, Regions, Rel, Sides, Size, SpecificAlignment,
-    VAlignment,
+    Abs, AlignElem, Alignment, Axes, ColumnsElem, Dir, Frame, FrameItem, HAlignment,
+    LayoutMultiple, Length, OuterVAlignment, Point, Ratio, Regions, Rel, Sides, Size,
+    SpecificAlignment, VAlignment,
 };
 
 use crate::model::Numbering;
+use crate::syntax::Span;
 use crate::text::TextElem;
 use crate::utils::{NonZeroExt, Numeric, Scalar};
-use crate::visualize::Paint;
+use crate::visualize::{Color, FixedStroke, Geometry, Paint};
 
 /// Layouts its child onto one or multiple pages.
 ///
@@ -191,6 +192,28 @@ pub struct PageElem {
     #[borrowed]
     pub fill: Option<Paint>,
 
+    /// How far the page extends beyond its trimmed edges.
+    ///
+    /// Professional printers print onto larger sheets and cut them down to
+    /// size afterwards. To avoid white slivers at the edges, backgrounds that
+    /// should reach the edge must extend into this extra area, the _bleed_.
+    /// The bleed is added around the page's `width` and `height`, and the
+    /// page's `fill` covers it. The PDF export marks the trimmed page with a
+    /// trim box and the bleed with a bleed box.
+    ///
+    /// ```typ
+    /// #set page(bleed: 3mm, marks: true)
+    /// ```
+    #[resolve]
+    pub bleed: Length,
+
+    /// Whether to draw crop marks that show the printer where to cut.
+    ///
+    /// The marks are placed in an extra area around the bleed, which enlarges
+    /// the exported page.
+    #[default(false)]
+    pub marks: bool,
+
     /// How to [number]($numbering) the pages.
     ///
     /// If an explicit `footer` (or `header` for top-aligned numbering) is
@@ -324,6 +347,27 @@ pub struct PageElem {
     #[borrowed]
     pub foreground: Option<Content>,
 
+    /// How a presentation viewer should transition to the page.
+    ///
+    /// This is only supported by the PDF export and only takes effect when the
+    /// PDF is shown in a viewer's presentation mode.
+    ///
+    /// ```typ
+    /// #set page(transition: "dissolve")
+    /// ```
+    pub transition: Option<Transition>,
+
+    /// How long a presentation viewer should show the page before it
+    /// automatically advances to the next one.
+    ///
+    /// If this is `{none}`, the viewer only advances when asked to. Like
+    /// `transition`, this is only supported by the PDF export.
+    ///
+    /// ```typ
+    /// #set page(duration: duration(seconds: 10))
+    /// ```
+    pub duration: Option<Duration>,
+
     /// The contents of the page(s).
     ///
     /// Multiple pages will be created if the content does not fit on a single
@@ -418,6 +462,10 @@ impl Packed<PageElem> {
         let header_ascent = self.header_ascent(styles);
         let footer_descent = self.footer_descent(styles);
         let numbering = self.numbering(styles);
+        let transition = self.transition(styles);
+        let duration = self.duration(styles);
+        let bleed = self.bleed(styles);
+        let marks = self.marks(styles);
         let number_align = self.number_align(styles);
 
         // Construct the numbering (for header or footer).
@@ -514,16 +562,34 @@ impl Packed<PageElem> {
                 }
             }
 
+            // Realize bleed.
+            frame.set_size(frame.size() + Size::splat(2.0 * bleed));
+            frame.translate(Point::splat(bleed));
+
             if let Some(fill) = fill {
                 frame.fill(fill.clone());
             }
 
-            page_counter.visit(engine, &frame)?;
-            pages.push(Page {
+            let mut page = Page {
                 frame,
+                margin,
+                columns,
                 numbering: numbering.clone(),
                 number: page_counter.logical(),
-            });
+                transition,
+                duration,
+                bleed,
+                slug: Abs::zero(),
+            };
+
+            if marks {
+                page.add_crop_marks();
+            }
+
+            check_contrast(engine, &page.frame, None);
+
+            page_counter.visit(engine, &page.frame)?;
+            pages.push(page);
 
             page_counter.step();
         }
@@ -532,18 +598,136 @@ impl Packed<PageElem> {
     }
 }
 
+/// The minimum contrast ratio between text and its background. Below this,
+/// a warning is emitted. This is the WCAG threshold for large text.
+const MIN_CONTRAST: f32 = 3.0;
+
+/// Warns about text whose fill has too little contrast with the solid
+/// background it is placed on.
+///
+/// A frame's background is given by a filled rectangle that covers the whole
+/// frame as its first item, like the ones added for page, block, and box fills.
+fn check_contrast(engine: &mut Engine, frame: &Frame, mut background: Option<Color>) {
+    let opaque = |paint: &Paint| match paint {
+        Paint::Solid(color) if color.alpha().map_or(true, |a| a >= 1.0) => Some(*color),
+        _ => None,
+    };
+
+    for (i, (_, item)) in frame.items().enumerate() {
+        match item {
+            FrameItem::Shape(shape, _) if i == 0 => {
+                if let (Geometry::Rect(size), Some(fill)) = (&shape.geometry, &shape.fill)
+                {
+                    if size.fits(frame.size()) {
+                        background = opaque(fill);
+                    }
+                }
+            }
+            FrameItem::Group(group) => check_contrast(engine, &group.frame, background),
+            FrameItem::Text(text) => {
+                let (Some(background), Some(fill)) = (background, opaque(&text.fill))
+                else {
+                    continue;
+                };
+
+                let Some(span) = text.glyphs.first().map(|glyph| glyph.span.0) else {
+                    continue;
+                };
+
+                let ratio = fill.contrast_ratio(background);
+                if ratio < MIN_CONTRAST && !span.is_detached() {
+                    engine.tracer.warn(warning!(
+                        span,
+                        "text has too little contrast with its background";
+                        hint: "the contrast ratio is {ratio:.2}:1, but at least \
+                               {MIN_CONTRAST}:1 is needed for the text to be readable",
+                    ).with_code("low-contrast"));
+                }
+            }
+            _ => {}
+        }
+    }
+}
+
 /// A finished page.
 #[derive(Debug, Clone)]
 pub struct Page {
     /// The frame that defines the page.
     pub frame: Frame,
+    /// The page's resolved margins.
+    pub margin: Sides<Abs>,
+    /// How many columns the page's body is laid out in.
+    pub columns: NonZeroUsize,
     /// The page's numbering.
     pub numbering: Option<Numbering>,
     /// The logical page number (controlled by `counter(page)` and may thus not
     /// match the physical number).
     pub number: usize,
+    /// How a presentation viewer should transition to the page.
+    pub transition: Option<Transition>,
+    /// How long a presentation viewer should show the page.
+    pub duration: Option<Duration>,
+    /// The bleed around the trimmed page, which is part of the frame.
+    pub bleed: Abs,
+    /// The area for printer's marks around the bleed, which is part of the
+    /// frame.
+    pub slug: Abs,
 }
 
+impl Page {
+    /// The size of the page after trimming, i.e. without bleed and the area
+    /// for printer's marks.
+    pub fn trim_size(&self) -> Size {
+        self.frame.size() - Size::splat(2.0 * (self.bleed + self.slug))
+    }
+
+    /// Surround the page with an area that holds crop marks at the corners of
+    /// its trimmed part.
+    ///
+    /// Does nothing if the page already has crop marks.
+    pub fn add_crop_marks(&mut self) {
+        if !self.slug.is_zero() {
+            return;
+        }
+
+        let bleed = self.bleed;
+        let trim = self.trim_size();
+        let frame = &mut self.frame;
+        frame.set_size(frame.size() + Size::splat(2.0 * SLUG));
+        frame.translate(Point::splat(SLUG));
+
+        let stroke = FixedStroke {
+            paint: Color::BLACK.into(),
+            thickness: Abs::pt(0.25),
+            ..FixedStroke::default()
+        };
+
+        // The marks start a bit outside of the bleed and point away from the
+        // page, so that they are cut off.
+        let start = bleed + Abs::pt(3.0);
+        let length = bleed + SLUG - start;
+        let origin = SLUG + bleed;
+        for (x, dx) in [(origin, -1.0), (origin + trim.x, 1.0)] {
+            for (y, dy) in [(origin, -1.0), (origin + trim.y, 1.0)] {
+                let horizontal = Geometry::Line(Point::with_x(length * dx));
+                let shape = horizontal.stroked(stroke.clone());
+                let pos = Point::new(x + start * dx, y);
+                frame.push(pos, FrameItem::Shape(shape, Span::detached()));
+
+                let vertical = Geometry::Line(Point::with_y(length * dy));
+                let shape = vertical.stroked(stroke.clone());
+                let pos = Point::new(x, y + start * dy);
+                frame.push(pos, FrameItem::Shape(shape, Span::detached()));
+            }
+        }
+
+        self.slug = SLUG;
+    }
+}
+
+/// The size of the area around the bleed that holds crop marks.
+const SLUG: Abs = Abs::raw(18.0);
+
 /// Specification of the page's margins.
 #[derive(Debug, Copy, Clone, Eq, PartialEq, Hash)]
 pub struct Margin {
@@ -730,6 +914,7 @@ cast! {
 /// A list of page ranges to be exported. The ranges are one-indexed.
 /// For example, `1..=3` indicates the first, second and third pages should be
 /// exported.
+#[derive(Debug, Clone)]
 pub struct PageRanges(Vec<PageRange>);
 
 pub type PageRange = RangeInclusive<Option<NonZeroUsize>>;
@@ -793,6 +978,34 @@ pub struct PagebreakElem {
     pub to: Option<Parity>,
 }
 
+/// A visual effect with which a presentation viewer transitions to a page.
+#[derive(Debug, Copy, Clone, Eq, PartialEq, Hash, Cast)]
+pub enum Transition {
+    /// Two lines sweep across the screen to reveal the page.
+    Split,
+    /// Multiple lines sweep across the screen to reveal the page.
+    Blinds,
+    /// A rectangular box sweeps inward from the edges or outward from the
+    /// center.
+    Box,
+    /// A single line sweeps across the screen.
+    Wipe,
+    /// The old page dissolves gradually.
+    Dissolve,
+    /// Like dissolve, but the effect sweeps across the screen.
+    Glitter,
+    /// The new page flies in.
+    Fly,
+    /// The new page pushes the old page off the screen.
+    Push,
+    /// The new page slides onto the screen, covering the old page.
+    Cover,
+    /// The old page slides off the screen, uncovering the new page.
+    Uncover,
+    /// The new page fades in.
+    Fade,
+}
+
 /// Whether something should be even or odd.
 #[derive(Debug, Copy, Clone, Eq, PartialEq, Hash, Cast)]
 pub enum Parity {
@@ -2,7 +2,7 @@ use crate::diag::{bail, SourceResult};
 use crate::engine::Engine;
 use crate::foundations::{elem, Content, Packed, Resolve, StyleChain};
 use crate::layout::{
-    Abs, AlignElem, Axes, Fragment, Frame, LayoutMultiple, Point, Regions, Size,
+    Abs, AlignElem, Axes, Fragment, Frame, LayoutMultiple, Length, Point, Regions, Size,
 };
 use crate::utils::Numeric;
 
@@ -11,7 +11,8 @@ use crate::utils::Numeric;
 /// This can be useful when implementing a custom index, reference, or outline.
 ///
 /// Space may be inserted between the instances of the body parameter, so be
-/// sure to include negative space if you need the instances to overlap.
+/// sure to include negative space if you need the instances to overlap. To
+/// keep the instances tightly packed, set `justify` to `{false}`.
 ///
 /// Errors if there no bounds on the available space, as it would create
 /// infinite content.
@@ -32,6 +33,31 @@ pub struct RepeatElem {
     /// The content to repeat.
     #[required]
     pub body: Content,
+
+    /// The gap between each instance of the body.
+    ///
+    /// The gap may be negative to make the instances overlap, but it must be
+    /// greater than the negated width of the body.
+    ///
+    /// ```example
+    /// #box(width: 1fr, repeat(gap: 0.5em)[.])
+    /// ```
+    #[resolve]
+    #[default]
+    pub gap: Length,
+
+    /// Whether to increase the gap between instances to completely fill the
+    /// available space.
+    ///
+    /// If this is `{false}`, the instances are placed with exactly the given
+    /// `gap` and the remaining space is distributed according to the current
+    /// horizontal alignment.
+    ///
+    /// ```example
+    /// #box(width: 1fr, repeat(justify: false)[. ])
+    /// ```
+    #[default(true)]
+    pub justify: bool,
 }
 
 impl LayoutMultiple for Packed<RepeatElem> {
@@ -46,11 +72,20 @@ impl LayoutMultiple for Packed<RepeatElem> {
         let piece = self.body().layout(engine, styles, pod)?.into_frame();
         let align = AlignElem::alignment_in(styles).resolve(styles);
 
+        let gap = self.gap(styles);
+        let justify = self.justify(styles);
+
+        // We need `count * width + (count - 1) * gap <= fill`, with `count`
+        // being a whole number.
         let fill = regions.size.x;
         let width = piece.width();
-        let count = (fill / width).floor();
-        let remaining = fill % width;
-        let apart = remaining / (count - 1.0);
+        if width > Abs::zero() && width + gap <= Abs::zero() {
+            bail!(self.span(), "gap must be greater than the negated width of the body");
+        }
+
+        let count = ((fill + gap) / (width + gap)).floor();
+        let remaining = (fill + gap) % (width + gap);
+        let apart = if justify { gap + remaining / (count - 1.0) } else { gap };
 
         let size = Size::new(regions.size.x, piece.height());
 
@@ -64,14 +99,14 @@ impl LayoutMultiple for Packed<RepeatElem> {
         }
 
         let mut offset = Abs::zero();
-        if count == 1.0 {
+        if count == 1.0 || !justify {
             offset += align.x.position(remaining);
         }
 
         if width > Abs::zero() {
             for _ in 0..(count as usize).min(1000) {
                 frame.push_frame(Point::with_x(offset), piece.clone());
-                offset += piece.width() + apart;
+                offset += width + apart;
             }
         }
 
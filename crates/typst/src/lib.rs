@@ -45,6 +45,7 @@ pub mod introspection;
 pub mod layout;
 pub mod loading;
 pub mod math;
+pub mod memory;
 pub mod model;
 pub mod realize;
 pub mod symbols;
@@ -57,6 +58,7 @@ pub use typst_syntax as syntax;
 pub use typst_utils as utils;
 
 use std::collections::HashSet;
+use std::num::NonZeroUsize;
 use std::ops::{Deref, Range};
 
 use comemo::{Track, Tracked, Validate};
@@ -86,8 +88,45 @@ use crate::visualize::Color;
 /// Requires a mutable reference to a tracer. Such a tracer can be created with
 /// `Tracer::new()`. Independently of whether compilation succeeded, calling
 /// `tracer.warnings()` after compilation will return all compiler warnings.
-#[typst_macros::time(name = "compile")]
 pub fn compile(world: &dyn World, tracer: &mut Tracer) -> SourceResult<Document> {
+    compile_with(world, tracer, MAX_ITERATIONS)
+}
+
+/// Compile a string of Typst markup with the given fonts.
+///
+/// This is a convenience wrapper around [`compile`] for simple use cases. It
+/// sets up a [`MemoryWorld`](memory::MemoryWorld) with the text as its main
+/// file. To access other files, use a `MemoryWorld` directly.
+///
+/// Returns the compilation result alongside all warnings.
+pub fn compile_str(
+    text: &str,
+    fonts: impl IntoIterator<Item = Font>,
+) -> (SourceResult<Document>, EcoVec<SourceDiagnostic>) {
+    let mut world = memory::MemoryWorld::new(text);
+    for font in fonts {
+        world.add_font(font);
+    }
+    let mut tracer = Tracer::new();
+    let result = compile(&world, &mut tracer);
+    (result, tracer.warnings())
+}
+
+/// The default limit for how often a document is laid out until introspection
+/// converges.
+pub const MAX_ITERATIONS: NonZeroUsize = NonZeroUsize::MIN.saturating_add(4);
+
+/// Compile a source file like [`compile`], but with a custom limit for how
+/// often the document is laid out until introspection converges.
+///
+/// If the limit is reached, the document from the last attempt is returned
+/// along with a warning.
+#[typst_macros::time(name = "compile")]
+pub fn compile_with(
+    world: &dyn World,
+    tracer: &mut Tracer,
+    max_iterations: NonZeroUsize,
+) -> SourceResult<Document> {
     // Call `track` on the world just once to keep comemo's ID stable.
     let world = world.track();
 
@@ -101,7 +140,7 @@ pub fn compile(world: &dyn World, tracer: &mut Tracer) -> SourceResult<Document>
     .map_err(deduplicate)?;
 
     // Typeset the module's content, relayouting until convergence.
-    typeset(world, tracer, &module.content()).map_err(deduplicate)
+    typeset(world, tracer, &module.content(), max_iterations).map_err(deduplicate)
 }
 
 /// Relayout until introspection converges.
@@ -109,6 +148,7 @@ fn typeset(
     world: Tracked<dyn World + '_>,
     tracer: &mut Tracer,
     content: &Content,
+    max_iterations: NonZeroUsize,
 ) -> SourceResult<Document> {
     // The name of the iterations for timing scopes.
     const ITER_NAMES: &[&str] =
@@ -121,9 +161,10 @@ fn typeset(
     let mut document = Document::default();
 
     // Relayout until all introspections stabilize.
-    // If that doesn't happen within five attempts, we give up.
+    // If that doesn't happen within the iteration limit, we give up.
     loop {
-        let _scope = TimingScope::new(ITER_NAMES[iter], None);
+        let name = ITER_NAMES.get(iter).copied().unwrap_or("typeset (6+)");
+        let _scope = TimingScope::new(name, None);
 
         // Clear delayed errors.
         tracer.delayed();
@@ -139,19 +180,19 @@ fn typeset(
         };
 
         // Layout!
-        document = content.layout_root(&mut engine, styles)?;
-        document.introspector.rebuild(&document.pages);
+        let mut new = content.layout_root(&mut engine, styles)?;
+        new.introspector.rebuild(&new.pages);
+        let previous = std::mem::replace(&mut document, new);
         iter += 1;
 
         if timed!("check stabilized", document.introspector.validate(&constraint)) {
             break;
         }
 
-        if iter >= 5 {
-            tracer.warn(warning!(
-                Span::detached(), "layout did not converge within 5 attempts";
-                hint: "check if any states or queries are updating themselves"
-            ));
+        if iter >= max_iterations.get() {
+            // In the first attempt, there is no earlier layout to compare to.
+            let previous = (iter > 1).then_some(&previous);
+            tracer.warn(convergence_warning(iter, &document, previous));
             break;
         }
     }
@@ -165,6 +206,31 @@ fn typeset(
     Ok(document)
 }
 
+/// Create the warning for a layout that did not converge.
+///
+/// The warning points to the first element that still changed in the last
+/// attempt, as it is a likely culprit. This requires the layout from the
+/// attempt before.
+fn convergence_warning(
+    iter: usize,
+    document: &Document,
+    previous: Option<&Document>,
+) -> SourceDiagnostic {
+    let changed = previous.and_then(|previous| {
+        document.introspector.changed_since(&previous.introspector).next()
+    });
+    let mut warning = warning!(
+        changed.map_or(Span::detached(), |elem| elem.span()),
+        "layout did not converge within {} attempts", iter;
+        hint: "check if any states or queries are updating themselves"
+    )
+    .with_code("non-converged");
+    if changed.is_some() {
+        warning.hint("this element still changed in the last attempt");
+    }
+    warning
+}
+
 /// Deduplicate diagnostics.
 fn deduplicate(mut diags: EcoVec<SourceDiagnostic>) -> EcoVec<SourceDiagnostic> {
     let mut unique = HashSet::new();
@@ -230,6 +296,18 @@ pub trait World: Send + Sync {
     fn packages(&self) -> &[(PackageSpec, Option<EcoString>)] {
         &[]
     }
+
+    /// Convert the text of a raw block in the given language into an SVG
+    /// image, e.g. by running an external diagram tool.
+    ///
+    /// This function is optional to implement. If it returns `None`, there is
+    /// no converter for the language and the raw block is displayed as text.
+    /// Since documents can only pick the language, the embedder remains in
+    /// full control over which tools run. Conversions should be cached as they
+    /// may be requested repeatedly for the same text.
+    fn convert_raw(&self, _lang: &str, _text: &str) -> Option<FileResult<Bytes>> {
+        None
+    }
 }
 
 macro_rules! delegate_for_ptr {
@@ -266,6 +344,10 @@ macro_rules! delegate_for_ptr {
             fn packages(&self) -> &[(PackageSpec, Option<EcoString>)] {
                 self.deref().packages()
             }
+
+            fn convert_raw(&self, lang: &str, text: &str) -> Option<FileResult<Bytes>> {
+                self.deref().convert_raw(lang, text)
+            }
         }
     };
 }
@@ -0,0 +1,210 @@
+//! An in-memory world for embedding the compiler.
+
+use std::collections::HashMap;
+
+use crate::diag::{bail, FileError, FileResult, StrResult};
+use crate::foundations::{Bytes, Datetime};
+use crate::syntax::{FileId, Source, VirtualPath};
+use crate::text::{Font, FontBook};
+use crate::utils::LazyHash;
+use crate::{Library, World};
+
+/// A [`World`] that keeps all of its fonts and files in memory.
+///
+/// This is the simplest way to embed the compiler into an application that
+/// doesn't need access to the file system or to packages. Fonts and files
+/// must be registered up front, everything else is reported as not found.
+///
+/// ```ignore
+/// let mut world = MemoryWorld::new("#image(\"logo.png\")");
+/// world.add_fonts(Bytes::from(font_data));
+/// world.add_file("logo.png", Bytes::from(logo_data))?;
+/// let document = typst::compile(&world, &mut Tracer::new());
+/// ```
+#[derive(Debug, Clone)]
+pub struct MemoryWorld {
+    /// The standard library.
+    library: LazyHash<Library>,
+    /// Metadata about all registered fonts.
+    book: LazyHash<FontBook>,
+    /// The registered fonts, in the same order as in the book.
+    fonts: Vec<Font>,
+    /// The id of the main source file.
+    main: FileId,
+    /// Registered source files.
+    sources: HashMap<FileId, Source>,
+    /// Registered binary files.
+    files: HashMap<FileId, Bytes>,
+    /// The date returned by `datetime.today()`.
+    today: Option<Datetime>,
+}
+
+impl MemoryWorld {
+    /// Create a new world whose main file `/main.typ` has the given text.
+    pub fn new(text: impl Into<String>) -> Self {
+        let main = FileId::new(None, VirtualPath::new("main.typ"));
+        let mut sources = HashMap::new();
+        sources.insert(main, Source::new(main, text.into()));
+        Self {
+            library: LazyHash::new(Library::default()),
+            book: LazyHash::new(FontBook::new()),
+            fonts: vec![],
+            main,
+            sources,
+            files: HashMap::new(),
+            today: None,
+        }
+    }
+
+    /// Use a custom standard library, e.g. one with `sys.inputs`.
+    pub fn with_library(mut self, library: Library) -> Self {
+        self.library = LazyHash::new(library);
+        self
+    }
+
+    /// Set the date returned by `datetime.today()`.
+    ///
+    /// If this is `None` (the default), the current date is unavailable.
+    pub fn with_today(mut self, today: Option<Datetime>) -> Self {
+        self.today = today;
+        self
+    }
+
+    /// Register a single font.
+    pub fn add_font(&mut self, font: Font) {
+        self.book.push(font.info().clone());
+        self.fonts.push(font);
+    }
+
+    /// Register all fonts in a font file or collection.
+    ///
+    /// Returns how many fonts were found in the data.
+    pub fn add_fonts(&mut self, data: Bytes) -> usize {
+        let mut count = 0;
+        for font in Font::iter(data) {
+            self.add_font(font);
+            count += 1;
+        }
+        count
+    }
+
+    /// Register a source file at the given path, replacing any previous file
+    /// at the same path.
+    pub fn add_source(&mut self, path: &str, text: impl Into<String>) -> FileId {
+        let id = FileId::new(None, VirtualPath::new(path));
+        self.files.remove(&id);
+        match self.sources.get_mut(&id) {
+            Some(source) => {
+                source.replace(&text.into());
+            }
+            None => {
+                self.sources.insert(id, Source::new(id, text.into()));
+            }
+        }
+        id
+    }
+
+    /// Register a binary file at the given path, replacing any previous file
+    /// at the same path.
+    ///
+    /// Fails if the path is the one of the main file, which must remain a
+    /// source file.
+    pub fn add_file(&mut self, path: &str, data: Bytes) -> StrResult<FileId> {
+        let id = FileId::new(None, VirtualPath::new(path));
+        if id == self.main {
+            bail!("cannot replace the main file with a binary file");
+        }
+        self.sources.remove(&id);
+        self.files.insert(id, data);
+        Ok(id)
+    }
+
+    /// Replace the text of the main file.
+    pub fn set_main(&mut self, text: impl Into<String>) {
+        if let Some(source) = self.sources.get_mut(&self.main) {
+            source.replace(&text.into());
+        }
+    }
+}
+
+impl World for MemoryWorld {
+    fn library(&self) -> &LazyHash<Library> {
+        &self.library
+    }
+
+    fn book(&self) -> &LazyHash<FontBook> {
+        &self.book
+    }
+
+    fn main(&self) -> Source {
+        self.sources[&self.main].clone()
+    }
+
+    fn source(&self, id: FileId) -> FileResult<Source> {
+        match self.sources.get(&id) {
+            Some(source) => Ok(source.clone()),
+            None if self.files.contains_key(&id) => Err(FileError::NotSource),
+            None => Err(not_found(id)),
+        }
+    }
+
+    fn file(&self, id: FileId) -> FileResult<Bytes> {
+        if let Some(data) = self.files.get(&id) {
+            return Ok(data.clone());
+        }
+        match self.sources.get(&id) {
+            Some(source) => Ok(Bytes::from(source.text().as_bytes())),
+            None => Err(not_found(id)),
+        }
+    }
+
+    fn font(&self, index: usize) -> Option<Font> {
+        self.fonts.get(index).cloned()
+    }
+
+    fn today(&self, _: Option<i64>) -> Option<Datetime> {
+        self.today
+    }
+}
+
+/// The error for a file that wasn't registered.
+fn not_found(id: FileId) -> FileError {
+    FileError::NotFound(id.vpath().as_rooted_path().into())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::eval::Tracer;
+
+    fn fonts() -> impl Iterator<Item = Font> {
+        typst_dev_assets::fonts().flat_map(|data| Font::iter(Bytes::from_static(data)))
+    }
+
+    #[test]
+    fn test_compile_str() {
+        let (result, _) = crate::compile_str("Hello", fonts());
+        assert_eq!(result.unwrap().pages.len(), 1);
+    }
+
+    #[test]
+    fn test_memory_world_files() {
+        let mut world = MemoryWorld::new(
+            "#import \"chapter.typ\": answer\n\
+             #assert.eq(answer, 42)\n\
+             #assert.eq(read(\"data.bin\", encoding: none), bytes((1, 2, 3)))",
+        );
+        world.add_source("chapter.typ", "#let answer = 42");
+        let id = world.add_file("data.bin", Bytes::from_static(&[1, 2, 3])).unwrap();
+        assert!(matches!(world.source(id), Err(FileError::NotSource)));
+        assert!(crate::compile(&world, &mut Tracer::new()).is_ok());
+    }
+
+    #[test]
+    fn test_memory_world_main_stays_source() {
+        let mut world = MemoryWorld::new("Hello");
+        assert!(world.add_file("main.typ", Bytes::from_static(b"World")).is_err());
+        assert_eq!(world.main().text(), "Hello");
+        assert_eq!(world.file(world.main).unwrap(), Bytes::from_static(b"Hello"));
+    }
+}
@@ -1,13 +1,25 @@
-use ecow::EcoString;
+use std::collections::{HashMap, VecDeque};
+use std::fmt::{self, Display, Formatter};
+use std::num::NonZeroUsize;
+
+use ecow::{eco_format, EcoString};
+use indexmap::IndexMap;
+use unicode_script::UnicodeScript;
 
 use crate::diag::{bail, SourceResult, StrResult};
 use crate::engine::Engine;
 use crate::foundations::{
-    cast, elem, Args, Array, Construct, Content, Datetime, Packed, Smart, StyleChain,
-    StyledElem, Value,
+    cast, elem, Args, Array, Cast, Construct, Content, Datetime, NativeElement, Packed,
+    Repr, Smart, StyleChain, StyledElem, Value,
 };
 use crate::introspection::{Introspector, ManualPageCounter};
-use crate::layout::{LayoutRoot, Page, PageElem};
+use crate::layout::{
+    Abs, Frame, FrameItem, LayoutRoot, Page, PageElem, Point, Size, Transform,
+};
+use crate::model::{Destination, HeadingElem, Numbering};
+use crate::syntax::Span;
+use crate::utils::hash128;
+use crate::visualize::Geometry;
 
 /// The root element of a document and its metadata.
 ///
@@ -57,6 +69,26 @@ pub struct DocumentElem {
     #[ghost]
     pub date: Smart<Option<Datetime>>,
 
+    /// How the document should be printed on both sides of the paper.
+    ///
+    /// This is only a hint embedded into the PDF. It preselects the duplex
+    /// mode in the print dialog of PDF viewers that support it.
+    #[ghost]
+    pub duplex: Option<Duplex>,
+
+    /// Whether PDF viewers may scale the pages to fit the paper when printing.
+    ///
+    /// If this is `{auto}` (default), the viewer's default applies. Setting it
+    /// to `{false}` asks the viewer to print the pages at their actual size,
+    /// which is important for documents that rely on exact dimensions, like
+    /// forms and labels.
+    #[ghost]
+    pub print_scaling: Smart<bool>,
+
+    /// The number of copies that should be preselected in the print dialog.
+    #[ghost]
+    pub copies: Option<NonZeroUsize>,
+
     /// The page runs.
     #[internal]
     #[variadic]
@@ -111,6 +143,9 @@ impl LayoutRoot for Packed<DocumentElem> {
             author: DocumentElem::author_in(styles).0,
             keywords: DocumentElem::keywords_in(styles).0,
             date: DocumentElem::date_in(styles),
+            duplex: DocumentElem::duplex_in(styles),
+            print_scaling: DocumentElem::print_scaling_in(styles),
+            copies: DocumentElem::copies_in(styles),
             introspector: Introspector::default(),
         })
     }
@@ -138,6 +173,17 @@ cast! {
     v: Array => Self(v.into_iter().map(Value::cast).collect::<StrResult<_>>()?),
 }
 
+/// How a document should be printed on both sides of the paper.
+#[derive(Debug, Copy, Clone, Eq, PartialEq, Hash, Cast)]
+pub enum Duplex {
+    /// Print on one side of the paper only.
+    Simplex,
+    /// Print on both sides and flip the paper on its short edge.
+    FlipShortEdge,
+    /// Print on both sides and flip the paper on its long edge.
+    FlipLongEdge,
+}
+
 /// A finished document with metadata and page frames.
 #[derive(Debug, Default, Clone)]
 pub struct Document {
@@ -151,13 +197,565 @@ pub struct Document {
     pub keywords: Vec<EcoString>,
     /// The document's creation date.
     pub date: Smart<Option<Datetime>>,
+    /// How the document should be printed on both sides of the paper.
+    pub duplex: Option<Duplex>,
+    /// Whether the pages may be scaled to fit the paper when printing.
+    pub print_scaling: Smart<bool>,
+    /// The number of copies to preselect when printing.
+    pub copies: Option<NonZeroUsize>,
     /// Provides the ability to execute queries on the document.
     pub introspector: Introspector,
 }
 
+impl Document {
+    /// The names and reserved sizes of all
+    /// [placeholders](crate::model::PlaceholderElem) in the document, in order
+    /// of appearance.
+    pub fn placeholders(&self) -> Vec<(EcoString, Size)> {
+        self.pages.iter().flat_map(|page| page.frame.placeholders()).collect()
+    }
+
+    /// Fill all [placeholders](crate::model::PlaceholderElem) with the given
+    /// name with an already layouted `replacement`, without relayouting the
+    /// document.
+    ///
+    /// The replacement is placed at the top-left corner of each reserved box.
+    /// Returns how many placeholders were filled and fails if the replacement
+    /// does not fit into one of the reserved boxes.
+    pub fn fill_placeholder(
+        &mut self,
+        name: &str,
+        replacement: &Frame,
+    ) -> StrResult<usize> {
+        let mut count = 0;
+        for page in &mut self.pages {
+            count += page.frame.fill_placeholder(name, replacement)?;
+        }
+        Ok(count)
+    }
+
+    /// Compares the visible contents of this document with another one, page
+    /// by page.
+    ///
+    /// Items are matched by their kind and, for text, their content. An item
+    /// that appears multiple times on a page is matched in order of
+    /// appearance. Matched items are reported if they moved or if they look
+    /// different, e.g. because of a different size, fill, font, or geometry.
+    /// This produces more actionable reports than comparing rendered pixels,
+    /// e.g. when checking for layout regressions.
+    pub fn diff(&self, new: &Document) -> Vec<Difference> {
+        let mut diffs = vec![];
+        if self.pages.len() != new.pages.len() {
+            diffs.push(Difference::PageCount {
+                old: self.pages.len(),
+                new: new.pages.len(),
+            });
+        }
+
+        for (i, (a, b)) in self.pages.iter().zip(&new.pages).enumerate() {
+            let page = i + 1;
+            if a.frame.size() != b.frame.size() {
+                diffs.push(Difference::PageSize {
+                    page,
+                    old: a.frame.size(),
+                    new: b.frame.size(),
+                });
+            }
+            diff_items(page, &a.frame, &b.frame, &mut diffs);
+        }
+
+        diffs
+    }
+
+    /// A fingerprint of each page's visual contents.
+    ///
+    /// Two pages with the same fingerprint look the same. Preview clients can
+    /// keep the fingerprints of a document around and pass them to
+    /// [`changed_pages`](Self::changed_pages) after the next compilation.
+    pub fn fingerprints(&self) -> Vec<u128> {
+        self.pages
+            .iter()
+            .map(|page| {
+                // Hashing the frame itself would also take spans and tags into
+                // account, which change with edits that don't affect the looks.
+                let mut items = vec![];
+                flatten(&page.frame, Transform::identity(), &mut items);
+                let items: Vec<_> = items
+                    .into_iter()
+                    .map(|(_, pos, appearance)| (pos, appearance))
+                    .collect();
+                hash128(&(page.frame.size(), items))
+            })
+            .collect()
+    }
+
+    /// The zero-based indices of the pages that differ from a previous
+    /// version of the document, given by its
+    /// [`fingerprints`](Self::fingerprints).
+    ///
+    /// Pages that didn't exist before are considered changed. This lets
+    /// preview clients only refetch the pages that changed.
+    pub fn changed_pages(&self, previous: &[u128]) -> Vec<usize> {
+        self.fingerprints()
+            .into_iter()
+            .enumerate()
+            .filter(|&(i, fingerprint)| previous.get(i) != Some(&fingerprint))
+            .map(|(i, _)| i)
+            .collect()
+    }
+
+    /// Calls `f` for each page in order, handing it the page's frame along
+    /// with resolved metadata.
+    ///
+    /// This is meant as a driver for exporters to custom formats that live
+    /// outside of Typst. Stops at and returns the first error of `f`.
+    ///
+    /// Page labels are only available for pages numbered with a pattern like
+    /// `{"i"}`. Numbering functions need the engine and thus can't be applied
+    /// at this point, so such pages have no label.
+    pub fn export_pages<E>(
+        &self,
+        mut f: impl FnMut(PageExport<'_>) -> Result<(), E>,
+    ) -> Result<(), E> {
+        // The plain-text titles of all headings with their page number.
+        let headings: Vec<_> = self
+            .introspector
+            .query(&HeadingElem::elem().select())
+            .iter()
+            .filter_map(|elem| {
+                let page = self.introspector.page(elem.location()?);
+                let heading = elem.to_packed::<HeadingElem>()?;
+                Some((page.get(), heading.body().plain_text()))
+            })
+            .collect();
+
+        for (i, page) in self.pages.iter().enumerate() {
+            let physical = i + 1;
+            let label = match &page.numbering {
+                Some(Numbering::Pattern(pattern)) => Some(pattern.apply(&[page.number])),
+                _ => None,
+            };
+            let section = headings
+                .iter()
+                .take_while(|(p, _)| *p <= physical)
+                .last()
+                .map(|(_, title)| title.clone());
+
+            let mut links = vec![];
+            collect_links(&page.frame, Transform::identity(), &mut links);
+
+            f(PageExport { page, physical, label, section, links })?;
+        }
+
+        Ok(())
+    }
+
+    /// The characters that no available font could display and that were
+    /// thus rendered as "tofus", in order of their first appearance.
+    pub fn missing_glyphs(&self) -> Vec<MissingGlyph> {
+        let mut missing = IndexMap::new();
+        for (i, page) in self.pages.iter().enumerate() {
+            collect_missing(&page.frame, i + 1, &mut missing);
+        }
+        missing.into_values().collect()
+    }
+}
+
+/// A character that could not be displayed with any available font, as found
+/// by [`Document::missing_glyphs`].
+#[derive(Debug, Clone, PartialEq)]
+pub struct MissingGlyph {
+    /// The character.
+    pub c: char,
+    /// The name of the character's Unicode script.
+    pub script: &'static str,
+    /// How often the character is missing in the document.
+    pub count: usize,
+    /// The physical, one-based number of the page it first appears on.
+    pub page: usize,
+    /// The source location of its first appearance.
+    pub span: Span,
+}
+
+/// A page along with resolved metadata, as handed out by
+/// [`Document::export_pages`].
+#[derive(Debug, Clone)]
+pub struct PageExport<'a> {
+    /// The page, including its frame.
+    pub page: &'a Page,
+    /// The physical, one-based page number.
+    pub physical: usize,
+    /// The page's logical number formatted with its numbering.
+    ///
+    /// This is `None` if the page isn't numbered or is numbered with a
+    /// function, which can't be applied after layout.
+    pub label: Option<EcoString>,
+    /// The plain-text title of the last heading on or before this page.
+    pub section: Option<EcoString>,
+    /// The links on this page with their position and size.
+    pub links: Vec<(Point, Size, Destination)>,
+}
+
+/// A semantic difference between two documents, as found by
+/// [`Document::diff`].
+///
+/// Page numbers are physical and one-based.
+#[derive(Debug, Clone, PartialEq)]
+pub enum Difference {
+    /// The documents have a different number of pages.
+    PageCount { old: usize, new: usize },
+    /// A page has a different size.
+    PageSize { page: usize, old: Size, new: Size },
+    /// An item only exists in the old document.
+    Removed { page: usize, item: EcoString, pos: Point },
+    /// An item only exists in the new document.
+    Added { page: usize, item: EcoString, pos: Point },
+    /// An item exists in both documents, but at a different position.
+    Moved { page: usize, item: EcoString, old: Point, new: Point },
+    /// An item exists in both documents, but looks different. The position is
+    /// the one in the new document.
+    Changed { page: usize, item: EcoString, pos: Point },
+}
+
+impl Display for Difference {
+    fn fmt(&self, f: &mut Formatter) -> fmt::Result {
+        match self {
+            Self::PageCount { old, new } => {
+                write!(f, "page count changed from {old} to {new}")
+            }
+            Self::PageSize { page, old, new } => write!(
+                f,
+                "page {page}: size changed from {} to {}",
+                fmt_pair(old.x, old.y),
+                fmt_pair(new.x, new.y),
+            ),
+            Self::Removed { page, item, pos } => {
+                write!(f, "page {page}: {item} at {} was removed", fmt_pair(pos.x, pos.y))
+            }
+            Self::Added { page, item, pos } => {
+                write!(f, "page {page}: {item} at {} was added", fmt_pair(pos.x, pos.y))
+            }
+            Self::Moved { page, item, old, new } => write!(
+                f,
+                "page {page}: {item} moved from {} to {}",
+                fmt_pair(old.x, old.y),
+                fmt_pair(new.x, new.y),
+            ),
+            Self::Changed { page, item, pos } => {
+                write!(f, "page {page}: {item} at {} changed", fmt_pair(pos.x, pos.y))
+            }
+        }
+    }
+}
+
+/// Formats a pair of lengths like `(10pt, 20pt)`.
+fn fmt_pair(x: Abs, y: Abs) -> EcoString {
+    eco_format!("({}, {})", x.repr(), y.repr())
+}
+
+/// Compares the visible items of two page frames.
+fn diff_items(page: usize, old: &Frame, new: &Frame, diffs: &mut Vec<Difference>) {
+    let mut old_items = vec![];
+    let mut new_items = vec![];
+    flatten(old, Transform::identity(), &mut old_items);
+    flatten(new, Transform::identity(), &mut new_items);
+
+    // Pair up the n-th occurrence of an item in the old frame with its n-th
+    // occurrence in the new frame.
+    let mut pending: HashMap<&EcoString, VecDeque<usize>> = HashMap::new();
+    for (i, (item, _, _)) in new_items.iter().enumerate() {
+        pending.entry(item).or_default().push_back(i);
+    }
+
+    let mut matched = vec![false; new_items.len()];
+    for (item, pos, looks) in &old_items {
+        match pending.get_mut(item).and_then(VecDeque::pop_front) {
+            Some(i) => {
+                matched[i] = true;
+                let (_, new_pos, new_looks) = new_items[i];
+                if !approx_eq(*pos, new_pos) {
+                    diffs.push(Difference::Moved {
+                        page,
+                        item: item.clone(),
+                        old: *pos,
+                        new: new_pos,
+                    });
+                }
+                if *looks != new_looks {
+                    diffs.push(Difference::Changed {
+                        page,
+                        item: item.clone(),
+                        pos: new_pos,
+                    });
+                }
+            }
+            None => {
+                diffs.push(Difference::Removed { page, item: item.clone(), pos: *pos })
+            }
+        }
+    }
+
+    for ((item, pos, _), matched) in new_items.iter().zip(matched) {
+        if !matched {
+            diffs.push(Difference::Added { page, item: item.clone(), pos: *pos });
+        }
+    }
+}
+
+/// Collects the visible items of a frame with a description, their position
+/// on the page, and a hash of their appearance.
+fn flatten(frame: &Frame, ts: Transform, out: &mut Vec<(EcoString, Point, u128)>) {
+    for (pos, item) in frame.items() {
+        let description = match item {
+            FrameItem::Group(group) => {
+                let ts = ts
+                    .pre_concat(Transform::translate(pos.x, pos.y))
+                    .pre_concat(group.transform);
+                flatten(&group.frame, ts, out);
+                continue;
+            }
+            FrameItem::Text(text) => eco_format!("text {}", text.text.repr()),
+            FrameItem::Shape(shape, _) => match shape.geometry {
+                Geometry::Line(_) => "line".into(),
+                Geometry::Rect(_) => "rectangle".into(),
+                Geometry::Path(_) => "path".into(),
+            },
+            FrameItem::Image(..) => "image".into(),
+            FrameItem::Link(..) => "link".into(),
+            FrameItem::Tag(_) => continue,
+        };
+        out.push((description, pos.transform(ts), appearance(item, ts)));
+    }
+}
+
+/// Hashes what an item looks like, independently of where it is.
+fn appearance(item: &FrameItem, ts: Transform) -> u128 {
+    // Only the linear part of the transform affects the item's looks. Spans
+    // are left out as they change with unrelated edits to the source.
+    let linear = (ts.sx, ts.ky, ts.kx, ts.sy);
+    match item {
+        FrameItem::Text(text) => {
+            let glyphs: Vec<_> =
+                text.glyphs.iter().map(|g| (g.id, g.x_advance, g.x_offset)).collect();
+            hash128(&(linear, &text.font, text.size, &text.fill, &text.stroke, glyphs))
+        }
+        FrameItem::Shape(shape, _) => hash128(&(linear, shape)),
+        FrameItem::Image(image, size, _) => hash128(&(linear, image, size)),
+        FrameItem::Link(dest, size) => hash128(&(linear, dest, size)),
+        FrameItem::Group(_) | FrameItem::Tag(_) => 0,
+    }
+}
+
+/// Collects the links in a frame with their position on the page.
+fn collect_links(
+    frame: &Frame,
+    ts: Transform,
+    out: &mut Vec<(Point, Size, Destination)>,
+) {
+    for (pos, item) in frame.items() {
+        match item {
+            FrameItem::Group(group) => {
+                let ts = ts
+                    .pre_concat(Transform::translate(pos.x, pos.y))
+                    .pre_concat(group.transform);
+                collect_links(&group.frame, ts, out);
+            }
+            FrameItem::Link(dest, size) => {
+                out.push((pos.transform(ts), *size, dest.clone()));
+            }
+            _ => {}
+        }
+    }
+}
+
+/// Collects the characters that were shaped as tofus in a frame.
+fn collect_missing(frame: &Frame, page: usize, out: &mut IndexMap<char, MissingGlyph>) {
+    for (_, item) in frame.items() {
+        match item {
+            FrameItem::Group(group) => collect_missing(&group.frame, page, out),
+            FrameItem::Text(text) => {
+                for glyph in text.glyphs.iter().filter(|glyph| glyph.id == 0) {
+                    for c in text.text[glyph.range()].chars() {
+                        if c.is_whitespace() || c.is_control() {
+                            continue;
+                        }
+                        out.entry(c)
+                            .or_insert_with(|| MissingGlyph {
+                                c,
+                                script: c.script().full_name(),
+                                count: 0,
+                                page,
+                                span: glyph.span.0,
+                            })
+                            .count += 1;
+                    }
+                }
+            }
+            _ => {}
+        }
+    }
+}
+
+/// Whether two points are approximately equal.
+fn approx_eq(a: Point, b: Point) -> bool {
+    a.x.approx_eq(b.x) && a.y.approx_eq(b.y)
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::eval::Tracer;
+    use crate::foundations::Bytes;
+    use crate::layout::Sides;
+    use crate::memory::MemoryWorld;
+    use crate::syntax::Span;
+    use crate::utils::NonZeroExt;
+    use crate::visualize::Color;
+    use crate::WorldExt;
+
+    fn pt(x: f64, y: f64) -> Point {
+        Point::new(Abs::pt(x), Abs::pt(y))
+    }
+
+    fn document(rects: &[Point], lines: &[Point]) -> Document {
+        let mut frame = Frame::hard(Size::splat(Abs::pt(100.0)));
+        let size = Size::splat(Abs::pt(5.0));
+        for &pos in rects {
+            let shape = Geometry::Rect(size).filled(Color::BLACK.into());
+            frame.push(pos, FrameItem::Shape(shape, Span::detached()));
+        }
+        for &pos in lines {
+            let shape = Geometry::Line(size.to_point()).filled(Color::BLACK.into());
+            frame.push(pos, FrameItem::Shape(shape, Span::detached()));
+        }
+        let page = Page {
+            frame,
+            margin: Sides::default(),
+            columns: NonZeroUsize::ONE,
+            numbering: None,
+            number: 1,
+            transition: None,
+            duration: None,
+            bleed: Abs::zero(),
+            slug: Abs::zero(),
+        };
+        Document { pages: vec![page], ..Document::default() }
+    }
+
+    #[test]
+    fn test_document_missing_glyphs() {
+        // None of the test fonts has glyphs for Tibetan.
+        let text = "A\u{F00}B \u{F00}";
+        let mut world = MemoryWorld::new(text);
+        for data in typst_dev_assets::fonts() {
+            world.add_fonts(Bytes::from_static(data));
+        }
+        let document = crate::compile(&world, &mut Tracer::new()).unwrap();
+        let missing = document.missing_glyphs();
+        assert_eq!(missing.len(), 1);
+
+        let glyph = &missing[0];
+        assert_eq!((glyph.c, glyph.count, glyph.page), ('\u{F00}', 2, 1));
+        assert_eq!(glyph.script, "Tibetan");
+
+        // The span points to the text that contains the first occurrence.
+        let range = world.range(glyph.span).unwrap();
+        assert!(range.contains(&text.find('\u{F00}').unwrap()));
+    }
+
+    #[test]
+    fn test_document_diff() {
+        let old = document(&[pt(0.0, 0.0), pt(10.0, 0.0)], &[pt(0.0, 50.0)]);
+        let new = document(&[pt(0.0, 0.0), pt(10.0, 5.0), pt(20.0, 0.0)], &[]);
+        assert_eq!(
+            old.diff(&new),
+            [
+                Difference::Moved {
+                    page: 1,
+                    item: "rectangle".into(),
+                    old: pt(10.0, 0.0),
+                    new: pt(10.0, 5.0),
+                },
+                Difference::Removed { page: 1, item: "line".into(), pos: pt(0.0, 50.0) },
+                Difference::Added {
+                    page: 1,
+                    item: "rectangle".into(),
+                    pos: pt(20.0, 0.0)
+                },
+            ]
+        );
+        assert_eq!(
+            old.diff(&new)[0].to_string(),
+            "page 1: rectangle moved from (10pt, 0pt) to (10pt, 5pt)",
+        );
+        assert!(new.diff(&new).is_empty());
+    }
+
+    #[test]
+    fn test_document_diff_looks() {
+        let old = document(&[pt(10.0, 0.0)], &[]);
+        let mut new = old.clone();
+        let frame = &mut new.pages[0].frame;
+        frame.clear();
+        let shape = Geometry::Rect(Size::splat(Abs::pt(5.0))).filled(Color::RED.into());
+        frame.push(pt(10.0, 0.0), FrameItem::Shape(shape, Span::detached()));
+        assert_eq!(
+            old.diff(&new),
+            [Difference::Changed {
+                page: 1,
+                item: "rectangle".into(),
+                pos: pt(10.0, 0.0)
+            }]
+        );
+        assert_eq!(
+            old.diff(&new)[0].to_string(),
+            "page 1: rectangle at (10pt, 0pt) changed"
+        );
+    }
+
+    #[test]
+    fn test_document_changed_pages() {
+        let old = document(&[pt(0.0, 0.0)], &[]);
+        let mut new = document(&[pt(0.0, 0.0)], &[]);
+        assert!(new.changed_pages(&old.fingerprints()).is_empty());
+
+        new.pages.push(old.pages[0].clone());
+        new.pages[0] = document(&[pt(5.0, 0.0)], &[]).pages.remove(0);
+        assert_eq!(new.changed_pages(&old.fingerprints()), [0, 1]);
+
+        // Only the looks matter, not how the items are grouped.
+        let mut grouped = document(&[], &[]);
+        let mut group = Frame::soft(Size::splat(Abs::pt(50.0)));
+        let shape = Geometry::Rect(Size::splat(Abs::pt(5.0))).filled(Color::BLACK.into());
+        group.push(pt(-5.0, 0.0), FrameItem::Shape(shape, Span::detached()));
+        grouped.pages[0].frame.push_frame(pt(5.0, 0.0), group);
+        assert!(grouped.changed_pages(&old.fingerprints()).is_empty());
+    }
+
+    #[test]
+    fn test_document_export_pages() {
+        let mut doc = document(&[], &[]);
+        let mut group = Frame::soft(Size::splat(Abs::pt(50.0)));
+        let dest = Destination::Position(crate::layout::Position {
+            page: NonZeroUsize::ONE,
+            point: Point::zero(),
+        });
+        group.push(pt(5.0, 5.0), FrameItem::Link(dest.clone(), Size::zero()));
+        doc.pages[0].frame.push_frame(pt(10.0, 20.0), group);
+        doc.pages[0].numbering = Some(Numbering::Pattern("i".parse().unwrap()));
+        doc.pages[0].number = 4;
+
+        let mut seen = vec![];
+        doc.export_pages(|export| {
+            seen.push((export.physical, export.label, export.section, export.links));
+            Ok::<_, ()>(())
+        })
+        .unwrap();
+        assert_eq!(
+            seen,
+            [(1, Some("iv".into()), None, vec![(pt(15.0, 25.0), Size::zero(), dest)])]
+        );
+    }
 
     #[test]
     fn test_document_is_send_and_sync() {
@@ -19,7 +19,7 @@ use crate::visualize::{LineElem, Stroke};
 /// Includes additional remarks and references on the same page with footnotes.
 /// A footnote will insert a superscript number that links to the note at the
 /// bottom of the page. Notes are numbered sequentially throughout your document
-/// and can break across multiple pages.
+/// (or [per page]($footnote.per-page)) and can break across multiple pages.
 ///
 /// To customize the appearance of the entry in the footnote listing, see
 /// [`footnote.entry`]($footnote.entry). The footnote itself is realized as a
@@ -55,9 +55,10 @@ pub struct FootnoteElem {
     /// How to number footnotes.
     ///
     /// By default, the footnote numbering continues throughout your document.
-    /// If you prefer per-page footnote numbering, you can reset the footnote
-    /// [counter] in the page [header]($page.header). In the future, there might
-    /// be a simpler way to achieve this.
+    /// If you prefer per-page footnote numbering, see the
+    /// [`per-page`]($footnote.per-page) parameter. To restart the numbering
+    /// elsewhere, for example in every chapter, you can update the footnote
+    /// [counter] with `{counter(footnote).update(0)}`.
     ///
     /// ```example
     /// #set footnote(numbering: "*")
@@ -70,6 +71,23 @@ pub struct FootnoteElem {
     #[default(Numbering::Pattern(NumberingPattern::from_str("1").unwrap()))]
     pub numbering: Numbering,
 
+    /// Whether to restart the footnote numbering on every page.
+    ///
+    /// This is often combined with symbol numbering, which runs out of
+    /// distinct markers quickly.
+    ///
+    /// ```example
+    /// #set page(height: 100pt)
+    /// #set footnote(numbering: "*", per-page: true)
+    ///
+    /// Star #footnote[On page one.]
+    /// #pagebreak()
+    /// Star again #footnote[On page two.]
+    /// and dagger #footnote[Also on page two.]
+    /// ```
+    #[default(false)]
+    pub per_page: bool,
+
     /// The content to put into the footnote. Can also be the label of another
     /// footnote this one should point to.
     #[required]
@@ -142,6 +160,10 @@ impl Count for Packed<FootnoteElem> {
     fn update(&self) -> Option<CounterUpdate> {
         (!self.is_ref()).then(|| CounterUpdate::Step(NonZeroUsize::ONE))
     }
+
+    fn restarts_per_page(&self) -> bool {
+        !self.is_ref() && self.per_page(StyleChain::default())
+    }
 }
 
 /// The body of a footnote can be either some content or a label referencing
@@ -1,5 +1,7 @@
 //! Structuring elements that define the document model.
 
+pub mod pdf;
+
 mod bibliography;
 mod cite;
 mod document;
@@ -15,6 +17,7 @@ mod list;
 mod numbering_;
 mod outline;
 mod par;
+mod placeholder;
 mod quote;
 mod reference;
 mod strong;
@@ -34,6 +37,7 @@ pub use self::list::*;
 pub use self::numbering_::*;
 pub use self::outline::*;
 pub use self::par::*;
+pub use self::placeholder::*;
 pub use self::quote::*;
 pub use self::reference::*;
 pub use self::strong::*;
@@ -71,5 +75,7 @@ pub fn define(global: &mut Scope) {
     global.define_elem::<TermsElem>();
     global.define_elem::<EmphElem>();
     global.define_elem::<StrongElem>();
+    global.define_elem::<PlaceholderElem>();
     global.define_func::<numbering>();
+    global.define_module(pdf::module());
 }
@@ -96,6 +96,24 @@ pub struct ParElem {
     #[resolve]
     pub hanging_indent: Length,
 
+    /// Whether to shape text across style boundaries.
+    ///
+    /// When enabled, adjacent pieces of text whose styles only differ in
+    /// properties that are applied after shaping (like the fill or
+    /// decorations) are shaped together. This way, coloring or underlining
+    /// part of a word doesn't change its kerning and ligatures.
+    ///
+    /// ```example
+    /// #set text(size: 20pt)
+    /// T#text(red)[o]ast
+    ///
+    /// #set par(shape-across-styles: true)
+    /// T#text(red)[o]ast
+    /// ```
+    #[ghost]
+    #[default(false)]
+    pub shape_across_styles: bool,
+
     /// Indicates wheter an overflowing line should be shrunk.
     ///
     /// This property is set to `false` on raw blocks, because shrinking a line
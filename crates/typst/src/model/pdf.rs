@@ -0,0 +1,99 @@
+//! PDF-specific functionality.
+
+use ecow::EcoString;
+
+use crate::diag::{At, SourceResult};
+use crate::engine::Engine;
+use crate::foundations::{
+    elem, Bytes, Cast, Content, Module, Packed, Scope, Show, StyleChain,
+};
+use crate::introspection::Locatable;
+use crate::syntax::Spanned;
+use crate::World;
+
+/// A module with PDF-specific definitions.
+pub fn module() -> Module {
+    let mut scope = Scope::deduplicating();
+    scope.define_elem::<EmbedElem>();
+    Module::new("pdf", scope)
+}
+
+/// A file that will be embedded into the output PDF.
+///
+/// This can be used to distribute additional files that are related to the
+/// PDF within it. PDF readers will display the files in a file listing.
+///
+/// Embedded files are also listed as associated files of the whole document,
+/// with the given [relationship]($pdf.embed.relationship). Some international
+/// standards build on this mechanism to embed machine-readable data (e.g.,
+/// ZUGFeRD/Factur-X for invoices) that mirrors the visual content of the PDF.
+/// Note that these standards typically have further requirements, like PDF/A-3
+/// conformance, that Typst does not fulfill by itself.
+///
+/// Each file is listed under the last component of its path. If multiple
+/// different files share a name, the later ones receive a numeric suffix.
+///
+/// The element produces no visible content and can be placed anywhere in the
+/// document. Other export formats ignore it.
+///
+/// # Example
+/// ```typ
+/// #pdf.embed(
+///   "experiment.csv",
+///   description: "Raw Oxygen readings from the Arctic experiment",
+///   mime-type: "text/csv",
+/// )
+/// ```
+#[elem(Show, Locatable)]
+pub struct EmbedElem {
+    /// Path to a file to be embedded.
+    ///
+    /// For more details, see the [Paths section]($syntax/#paths).
+    #[required]
+    #[parse(
+        let Spanned { v: path, span } =
+            args.expect::<Spanned<EcoString>>("path to the file to be embedded")?;
+        let id = span.resolve_path(&path).at(span)?;
+        let data = engine.world.file(id).at(span)?;
+        path
+    )]
+    #[borrowed]
+    pub path: EcoString,
+
+    /// The raw file data.
+    #[internal]
+    #[required]
+    #[parse(data)]
+    pub data: Bytes,
+
+    /// A description for the embedded file.
+    pub description: Option<EcoString>,
+
+    /// The [MIME type](https://www.iana.org/assignments/media-types) of the
+    /// embedded file.
+    pub mime_type: Option<EcoString>,
+
+    /// The relationship of the embedded file to the document.
+    ///
+    /// If this is `{none}`, the relationship is left unspecified.
+    pub relationship: Option<EmbeddedFileRelationship>,
+}
+
+impl Show for Packed<EmbedElem> {
+    fn show(&self, _: &mut Engine, _: StyleChain) -> SourceResult<Content> {
+        Ok(Content::empty())
+    }
+}
+
+/// The relationship of an embedded file with the document.
+#[derive(Debug, Copy, Clone, Eq, PartialEq, Hash, Cast)]
+pub enum EmbeddedFileRelationship {
+    /// The PDF document was created from the source file.
+    Source,
+    /// The file was used to derive a visual presentation in the PDF.
+    Data,
+    /// An alternative representation of the document.
+    Alternative,
+    /// Additional resources for the document.
+    Supplement,
+}
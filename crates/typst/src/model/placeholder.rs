@@ -0,0 +1,66 @@
+use ecow::EcoString;
+
+use crate::diag::SourceResult;
+use crate::engine::Engine;
+use crate::foundations::{elem, Content, NativeElement, Packed, Show, Smart, StyleChain};
+use crate::introspection::Locatable;
+use crate::layout::{BoxElem, Length, Rel, Sizing};
+
+/// A reserved box whose content is supplied when exporting the document.
+///
+/// Placeholders make it possible to lay out a document once and to then
+/// produce many personalized copies of it (for instance, with different
+/// serial numbers or recipient names) without relayouting. The document
+/// reserves a box of the given size and the application that embeds Typst
+/// fills in the final content at export time, as long as it fits into the
+/// reserved box.
+///
+/// Until a placeholder is filled, its body is shown instead.
+///
+/// # Example
+/// ```example
+/// Dear #placeholder("recipient", width: 3cm, height: 1em)[Jane Doe],
+///
+/// your ticket number is
+/// #placeholder("serial", width: 2cm, height: 1em)[000000].
+/// ```
+#[elem(Locatable, Show)]
+pub struct PlaceholderElem {
+    /// The name by which the embedding application refers to the placeholder.
+    ///
+    /// Multiple placeholders may share the same name. They are then all
+    /// filled with the same content.
+    #[required]
+    pub name: EcoString,
+
+    /// The width of the reserved box.
+    ///
+    /// If this is `{auto}`, the box takes the width of its body.
+    pub width: Smart<Rel<Length>>,
+
+    /// The height of the reserved box.
+    ///
+    /// If this is `{auto}`, the box takes the height of its body.
+    pub height: Smart<Rel<Length>>,
+
+    /// The content to show as long as the placeholder is not filled.
+    #[positional]
+    pub body: Option<Content>,
+}
+
+impl Show for Packed<PlaceholderElem> {
+    #[typst_macros::time(name = "placeholder", span = self.span())]
+    fn show(&self, _: &mut Engine, styles: StyleChain) -> SourceResult<Content> {
+        let width = match self.width(styles) {
+            Smart::Auto => Sizing::Auto,
+            Smart::Custom(rel) => Sizing::Rel(rel),
+        };
+
+        Ok(BoxElem::new()
+            .with_width(width)
+            .with_height(self.height(styles))
+            .with_body(self.body(styles))
+            .pack()
+            .spanned(self.span()))
+    }
+}
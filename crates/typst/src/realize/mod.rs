@@ -32,7 +32,7 @@ use crate::model::{
     ParbreakElem, TermItem, TermsElem,
 };
 use crate::syntax::Span;
-use crate::text::{LinebreakElem, SmartQuoteElem, SpaceElem, TextElem};
+use crate::text::{GlyphElem, LinebreakElem, SmartQuoteElem, SpaceElem, TextElem};
 
 /// Realize into a `DocumentElem`, an element that is capable of root-level
 /// layout.
@@ -451,6 +451,7 @@ impl<'a> ParBuilder<'a> {
             || content.is::<HElem>()
             || content.is::<LinebreakElem>()
             || content.is::<SmartQuoteElem>()
+            || content.is::<GlyphElem>()
             || content
                 .to_packed::<EquationElem>()
                 .is_some_and(|elem| !elem.block(styles))
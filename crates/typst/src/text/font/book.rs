@@ -210,6 +210,13 @@ impl FontInfo {
         (0..count).filter_map(move |index| Self::new(data, index))
     }
 
+    /// Whether the font covers every non-whitespace character of the text.
+    pub fn covers(&self, text: &str) -> bool {
+        text.chars()
+            .filter(|c| !c.is_whitespace())
+            .all(|c| self.coverage.contains(c as u32))
+    }
+
     /// Compute metadata for a single ttf-parser face.
     pub(super) fn from_ttf(ttf: &ttf_parser::Face) -> Option<Self> {
         let ps_name = find_name(ttf, name_id::POST_SCRIPT_NAME);
@@ -465,6 +472,11 @@ impl Coverage {
         false
     }
 
+    /// The number of covered codepoints.
+    pub fn count(&self) -> usize {
+        self.0.iter().skip(1).step_by(2).map(|&run| run as usize).sum()
+    }
+
     /// Iterate over all covered codepoints.
     pub fn iter(&self) -> impl Iterator<Item = u32> + '_ {
         let mut inside = false;
@@ -536,5 +548,6 @@ mod tests {
         let codepoints = vec![2, 3, 7, 8, 9, 14, 15, 19, 21];
         let coverage = Coverage::from_vec(codepoints.clone());
         assert_eq!(coverage.iter().collect::<Vec<_>>(), codepoints);
+        assert_eq!(coverage.count(), codepoints.len());
     }
 }
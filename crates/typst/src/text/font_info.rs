@@ -0,0 +1,100 @@
+use std::collections::BTreeSet;
+
+use ecow::EcoString;
+use ttf_parser::Tag;
+
+use crate::engine::Engine;
+use crate::foundations::{dict, func, Array, Dict, IntoValue, Str};
+use crate::text::FontFamily;
+use crate::World;
+
+/// Provides information about an available font family.
+///
+/// This lets templates adapt to the fonts at hand, for example by only using
+/// real small capitals when a font supports them.
+///
+/// Returns `{none}` if no font of the family is available. Otherwise, returns
+/// a dictionary with the following keys:
+/// - `family`: The name of the family as given by its fonts.
+/// - `variants`: An array with one dictionary per font in the family, each
+///   with the font's `style`, `weight`, and `stretch`.
+/// - `features`: The [OpenType feature tags]($text.features) supported by at
+///   least one font of the family, like `{"smcp"}` for small capitals.
+/// - `scripts`: The OpenType script tags supported by at least one font of the
+///   family, like `{"latn"}` for the Latin script.
+/// - `covers`: Whether the family has glyphs for all characters given to the
+///   `covers` argument. Only present if that argument is given.
+///
+/// ```example
+/// #let info = font-info(
+///   "Linux Libertine",
+///   covers: "ſ",
+/// )
+///
+/// #info.variants.len() variants \
+/// Long s: #info.covers \
+/// Small caps: #("smcp" in info.features)
+/// ```
+#[func]
+pub fn font_info(
+    /// The engine.
+    engine: &mut Engine,
+    /// The name of the font family.
+    family: FontFamily,
+    /// Characters to check the family's glyph coverage for.
+    #[named]
+    covers: Option<Str>,
+) -> Option<Dict> {
+    let world = engine.world;
+    let book = world.book();
+    let ids: Vec<usize> = book.select_family(family.as_str()).collect();
+    let name = book.info(*ids.first()?)?.family.as_str();
+
+    let mut variants = Array::new();
+    let mut features = BTreeSet::new();
+    let mut scripts = BTreeSet::new();
+    for &id in &ids {
+        if let Some(info) = book.info(id) {
+            let variant = info.variant;
+            variants.push(
+                dict! {
+                    "style" => variant.style,
+                    "weight" => variant.weight,
+                    "stretch" => variant.stretch,
+                }
+                .into_value(),
+            );
+        }
+
+        let Some(font) = world.font(id) else { continue };
+        let tables = font.ttf().tables();
+        for table in [tables.gsub, tables.gpos].into_iter().flatten() {
+            features.extend(table.features.into_iter().map(|f| tag_str(f.tag)));
+            scripts.extend(table.scripts.into_iter().map(|s| tag_str(s.tag)));
+        }
+    }
+
+    let mut info = dict! {
+        "family" => name,
+        "variants" => variants,
+        "features" => features.into_iter().map(IntoValue::into_value).collect::<Array>(),
+        "scripts" => scripts.into_iter().map(IntoValue::into_value).collect::<Array>(),
+    };
+
+    if let Some(text) = covers {
+        let covered = text.chars().all(|c| {
+            ids.iter()
+                .filter_map(|&id| book.info(id))
+                .any(|info| info.coverage.contains(c as u32))
+        });
+        info.insert("covers".into(), covered.into_value());
+    }
+
+    Some(info)
+}
+
+/// Convert an OpenType tag into a string.
+fn tag_str(tag: Tag) -> EcoString {
+    let bytes = tag.to_bytes();
+    std::str::from_utf8(&bytes).unwrap_or_default().trim_end().into()
+}
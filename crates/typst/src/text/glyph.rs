@@ -0,0 +1,107 @@
+use ecow::EcoString;
+
+use crate::diag::{bail, SourceResult};
+use crate::engine::Engine;
+use crate::foundations::{elem, Packed, PlainText, StyleChain};
+use crate::layout::{Em, Frame, FrameItem, Point, Size};
+use crate::text::{families, variant, FontFamily, Glyph, TextElem, TextItem};
+use crate::World;
+
+/// A specific glyph from a font, selected by its index.
+///
+/// Fonts often contain glyphs that can't be reached by typing a character,
+/// like ornaments or unencoded alternates. This function places such a glyph
+/// directly. The glyph is styled like the surrounding text and behaves like a
+/// single character within a paragraph.
+///
+/// If the glyph you are after is reachable through an OpenType feature (e.g.
+/// a swash or stylistic alternate), prefer enabling that feature with the
+/// [`features`]($text.features) parameter of the text function. Shaping then
+/// keeps working as usual and the text remains searchable.
+///
+/// # Example
+/// ```typ
+/// #glyph(1024, font: "Linux Libertine", alt: "❦")
+/// ```
+#[elem(PlainText)]
+pub struct GlyphElem {
+    /// The index of the glyph in the font, also known as its glyph ID.
+    ///
+    /// Font editors and tools like `ttx` show the index of each glyph.
+    #[required]
+    pub index: u16,
+
+    /// The font family to take the glyph from.
+    ///
+    /// If `{none}`, the first available family in the text's
+    /// [font list]($text.font) is used.
+    pub font: Option<FontFamily>,
+
+    /// The text that the glyph represents.
+    ///
+    /// This is what PDF readers extract when the glyph is copied or searched.
+    /// If `{none}`, the glyph is extracted as the Unicode replacement
+    /// character.
+    pub alt: Option<EcoString>,
+}
+
+impl Packed<GlyphElem> {
+    /// Layout the glyph into a frame whose baseline is set.
+    pub fn layout(&self, engine: &mut Engine, styles: StyleChain) -> SourceResult<Frame> {
+        let world = engine.world;
+        let variant = variant(styles);
+        let select = |family: &str| {
+            world.book().select(family, variant).and_then(|id| world.font(id))
+        };
+
+        let font = match self.font(styles) {
+            Some(family) => select(family.as_str()),
+            None => families(styles).find_map(select),
+        };
+        let Some(font) = font else {
+            bail!(self.span(), "no font could be found");
+        };
+
+        let id = *self.index();
+        let Some(advance) =
+            font.advance(id).filter(|_| id < font.ttf().number_of_glyphs())
+        else {
+            bail!(self.span(), "font does not contain a glyph with index {id}");
+        };
+
+        let size = TextElem::size_in(styles);
+        let bbox = font.ttf().glyph_bounding_box(ttf_parser::GlyphId(id));
+        let top = TextElem::top_edge_in(styles).resolve(size, &font, bbox);
+        let bottom = -TextElem::bottom_edge_in(styles).resolve(size, &font, bbox);
+
+        let text = self.alt(styles).unwrap_or_else(|| '\u{FFFD}'.into());
+        let item = TextItem {
+            font,
+            size,
+            fill: TextElem::fill_in(styles),
+            stroke: TextElem::stroke_in(styles).map(|s| s.unwrap_or_default()),
+            lang: TextElem::lang_in(styles),
+            region: TextElem::region_in(styles),
+            glyphs: vec![Glyph {
+                id,
+                x_advance: advance,
+                x_offset: Em::zero(),
+                range: 0..text.len() as u16,
+                span: (self.span(), 0),
+            }],
+            text,
+        };
+
+        let mut frame = Frame::soft(Size::new(item.width(), top + bottom));
+        frame.set_baseline(top);
+        frame.push(Point::with_y(top), FrameItem::Text(item));
+        Ok(frame)
+    }
+}
+
+impl PlainText for Packed<GlyphElem> {
+    fn plain_text(&self, text: &mut EcoString) {
+        let alt = self.alt.as_ref().and_then(Option::as_deref);
+        text.push_str(alt.unwrap_or("\u{FFFD}"));
+    }
+}
@@ -3,6 +3,9 @@
 mod case;
 mod deco;
 mod font;
+#[path = "font_info.rs"]
+mod font_info_;
+mod glyph;
 mod item;
 mod lang;
 mod linebreak;
@@ -18,6 +21,8 @@ mod space;
 pub use self::case::*;
 pub use self::deco::*;
 pub use self::font::*;
+pub use self::font_info_::*;
+pub use self::glyph::*;
 pub use self::item::*;
 pub use self::lang::*;
 pub use self::linebreak::*;
@@ -46,6 +51,7 @@ use crate::foundations::{
 use crate::layout::{Abs, Axis, Dir, Em, Length, Ratio, Rel};
 use crate::model::ParElem;
 use crate::syntax::Spanned;
+use crate::utils::Numeric;
 use crate::visualize::{Color, Paint, RelativeTo, Stroke};
 use crate::World;
 
@@ -61,6 +67,7 @@ pub(super) fn define(global: &mut Scope) {
     global.define_elem::<TextElem>();
     global.define_elem::<LinebreakElem>();
     global.define_elem::<SmartQuoteElem>();
+    global.define_elem::<GlyphElem>();
     global.define_elem::<SubElem>();
     global.define_elem::<SuperElem>();
     global.define_elem::<UnderlineElem>();
@@ -72,6 +79,7 @@ pub(super) fn define(global: &mut Scope) {
     global.define_func::<lower>();
     global.define_func::<upper>();
     global.define_func::<lorem>();
+    global.define_func::<font_info>();
 }
 
 /// Customizes the look and layout of text in a variety of ways.
@@ -136,7 +144,17 @@ pub struct TextElem {
                         font_list.span,
                         "unknown font family: {}",
                         family.as_str(),
-                    ));
+                    ).with_code("unknown-font-family"));
+                } else if book
+                    .select_family(family.as_str())
+                    .all(|id| engine.world.font(id).is_none())
+                {
+                    engine.tracer.warn(warning!(
+                        font_list.span,
+                        "failed to load font family: {}",
+                        family.as_str();
+                        hint: "the font files might be corrupt or unreadable",
+                    ).with_code("font-load-failed"));
                 }
             }
         }
@@ -587,16 +605,26 @@ pub struct TextElem {
     /// these ligatures by turning off the OpenType `liga` and `clig` font
     /// features.
     ///
+    /// When set to `{auto}`, ligatures are active unless
+    /// [tracking]($text.tracking) is applied, since spaced-out letters
+    /// shouldn't be merged. Moreover, no ligature is formed across a style
+    /// boundary (for instance when only one of the letters is colored) even if
+    /// the paragraph [shapes across styles]($par.shape-across-styles). With the
+    /// default of `{true}`, ligatures are always active.
+    ///
     /// ```example
     /// #set text(size: 20pt)
     /// A fine ligature.
     ///
     /// #set text(ligatures: false)
     /// A fine ligature.
+    ///
+    /// #set text(ligatures: auto, tracking: 2pt)
+    /// A fine ligature.
     /// ```
-    #[default(true)]
+    #[default(Smart::Custom(true))]
     #[ghost]
-    pub ligatures: bool,
+    pub ligatures: Smart<bool>,
 
     /// Whether ligatures that should be used sparingly are active. Setting this
     /// to `{true}` enables the OpenType `dlig` font feature.
@@ -1175,7 +1203,9 @@ pub(crate) fn features(styles: StyleChain) -> Vec<Feature> {
         feat(&storage, 1);
     }
 
-    if !TextElem::ligatures_in(styles) {
+    let ligatures = TextElem::ligatures_in(styles)
+        .unwrap_or_else(|| TextElem::tracking_in(styles).is_zero());
+    if !ligatures {
         feat(b"liga", 0);
         feat(b"clig", 0);
     }
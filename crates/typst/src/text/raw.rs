@@ -17,13 +17,14 @@ use crate::foundations::{
     PlainText, Show, ShowSet, Smart, StyleChain, Styles, Synthesize, Value,
 };
 use crate::layout::{BlockElem, Em, HAlignment};
+use crate::loading::Readable;
 use crate::model::{Figurable, ParElem};
 use crate::syntax::{split_newlines, LinkedNode, Span, Spanned};
 use crate::text::{
     FontFamily, FontList, Hyphenate, LinebreakElem, LocalName, SmartQuoteElem, TextElem,
     TextSize,
 };
-use crate::visualize::Color;
+use crate::visualize::{Color, ImageElem, ImageFormat, VectorFormat};
 use crate::{syntax, World};
 
 // Shorthand for highlighter closures.
@@ -155,6 +156,11 @@ pub struct RawElem {
     ///
     /// This is ```typ also *Typst*```, but inline!
     /// ````
+    ///
+    /// The environment Typst runs in can also register converters for some
+    /// languages, e.g. through the CLI's `--raw-converter` option. Raw blocks
+    /// in such a language are then displayed as the SVG image produced by the
+    /// converter instead of as text.
     #[borrowed]
     pub lang: Option<EcoString>,
 
@@ -417,7 +423,13 @@ impl Packed<RawElem> {
 
 impl Show for Packed<RawElem> {
     #[typst_macros::time(name = "raw", span = self.span())]
-    fn show(&self, _: &mut Engine, styles: StyleChain) -> SourceResult<Content> {
+    fn show(&self, engine: &mut Engine, styles: StyleChain) -> SourceResult<Content> {
+        if self.block(styles) {
+            if let Some(image) = self.convert(engine, styles)? {
+                return Ok(image);
+            }
+        }
+
         let lines = self.lines().map(|v| v.as_slice()).unwrap_or_default();
 
         let mut seq = EcoVec::with_capacity((2 * lines.len()).saturating_sub(1));
@@ -441,6 +453,30 @@ impl Show for Packed<RawElem> {
     }
 }
 
+impl Packed<RawElem> {
+    /// Convert the raw block into an image if the world has a converter for
+    /// its language.
+    fn convert(
+        &self,
+        engine: &Engine,
+        styles: StyleChain,
+    ) -> SourceResult<Option<Content>> {
+        let Some(lang) = self.lang(styles) else { return Ok(None) };
+        let Some(result) = engine.world.convert_raw(lang, &self.text().get()) else {
+            return Ok(None);
+        };
+
+        let span = self.span();
+        let data = result.at(span)?;
+        let image = ImageElem::new(EcoString::new(), Readable::Bytes(data))
+            .with_format(Smart::Custom(ImageFormat::Vector(VectorFormat::Svg)))
+            .pack()
+            .spanned(span)
+            .aligned(self.align(styles).into());
+        Ok(Some(BlockElem::new().with_body(Some(image)).pack().spanned(span)))
+    }
+}
+
 impl ShowSet for Packed<RawElem> {
     fn show_set(&self, styles: StyleChain) -> Styles {
         let mut out = Styles::new();
@@ -1229,6 +1229,23 @@ impl Color {
         }
     }
 
+    /// Computes the relative luminance of the color as defined by WCAG.
+    ///
+    /// The result is between zero (black) and one (white).
+    pub fn relative_luminance(self) -> f32 {
+        let [r, g, b, _] = self.to_linear_rgb().to_vec4();
+        0.2126 * r + 0.7152 * g + 0.0722 * b
+    }
+
+    /// Computes the contrast ratio between two colors as defined by WCAG.
+    ///
+    /// The result is between 1 (no contrast) and 21 (black on white).
+    pub fn contrast_ratio(self, other: Self) -> f32 {
+        let a = self.relative_luminance();
+        let b = other.relative_luminance();
+        (a.max(b) + 0.05) / (a.min(b) + 0.05)
+    }
+
     /// Sets the alpha channel of the color, if it has one.
     pub fn with_alpha(mut self, alpha: f32) -> Self {
         match &mut self {
@@ -1813,6 +1830,26 @@ cast! {
     v: Ratio => Self(v.get()),
 }
 
+/// A transformation that is applied to all colors of a finished document,
+/// e.g. to prepare it for print.
+#[derive(Debug, Copy, Clone, Eq, PartialEq, Hash)]
+pub enum ColorTransform {
+    /// Converts colors to shades of gray.
+    Grayscale,
+    /// Converts colors to CMYK, restricting them to a print gamut.
+    Cmyk,
+}
+
+impl ColorTransform {
+    /// Applies the transformation to a color.
+    pub fn apply(self, color: Color) -> Color {
+        match self {
+            Self::Grayscale => color.to_luma(),
+            Self::Cmyk => color.to_cmyk(),
+        }
+    }
+}
+
 /// A color space for color manipulation.
 #[derive(Debug, Copy, Clone, Eq, PartialEq, Hash)]
 pub enum ColorSpace {
@@ -12,7 +12,7 @@ use crate::foundations::{
 };
 use crate::layout::{Angle, Axes, Dir, Quadrant, Ratio};
 use crate::syntax::{Span, Spanned};
-use crate::visualize::{Color, ColorSpace, WeightedColor};
+use crate::visualize::{Color, ColorSpace, ColorTransform, WeightedColor};
 
 /// A color gradient.
 ///
@@ -755,6 +755,23 @@ impl Gradient {
 
         self
     }
+
+    /// Clones this gradient, but with a color transformation applied to all
+    /// stop colors.
+    pub fn map_colors(mut self, transform: ColorTransform) -> Self {
+        let stops = match &mut self {
+            Self::Linear(linear) => &mut Arc::make_mut(linear).stops,
+            Self::Radial(radial) => &mut Arc::make_mut(radial).stops,
+            Self::Conic(conic) => &mut Arc::make_mut(conic).stops,
+        };
+
+        for (color, _) in stops {
+            *color = transform.apply(*color);
+        }
+
+        self
+    }
+
     /// Returns a reference to the stops of this gradient.
     pub fn stops_ref(&self) -> &[(Color, Ratio)] {
         match self {
@@ -8,6 +8,7 @@ pub use self::svg::SvgImage;
 
 use std::ffi::OsStr;
 use std::fmt::{self, Debug, Formatter};
+use std::io;
 use std::sync::Arc;
 
 use comemo::Tracked;
@@ -16,24 +17,24 @@ use ecow::EcoString;
 use crate::diag::{bail, At, SourceResult, StrResult};
 use crate::engine::Engine;
 use crate::foundations::{
-    cast, elem, func, scope, Bytes, Cast, Content, NativeElement, Packed, Resolve, Smart,
-    StyleChain,
+    cast, dict, elem, func, scope, Bytes, Cast, Content, Dict, NativeElement, Packed,
+    Resolve, Smart, StyleChain,
 };
 use crate::layout::{
-    Abs, Axes, FixedAlignment, Frame, FrameItem, LayoutSingle, Length, Point, Regions,
-    Rel, Size,
+    Abs, Axes, FixedAlignment, Frame, FrameItem, LayoutSingle, Length, Point, Ratio,
+    Regions, Rel, Sides, Size,
 };
 use crate::loading::Readable;
 use crate::model::Figurable;
 use crate::syntax::{Span, Spanned};
 use crate::text::{families, LocalName};
 use crate::utils::LazyHash;
-use crate::visualize::Path;
+use crate::visualize::{Color, ColorTransform, Path};
 use crate::World;
 
 /// A raster or vector graphic.
 ///
-/// Supported formats are PNG, JPEG, GIF and SVG.
+/// Supported formats are PNG, JPEG, GIF, WebP and SVG.
 ///
 /// _Note:_ Work on SVG export is ongoing and there might be visual inaccuracies
 /// in the resulting PDF. Make sure to double-check embedded SVG images. If you
@@ -96,6 +97,18 @@ pub struct ImageElem {
     /// ```
     #[default(ImageFit::Cover)]
     pub fit: ImageFit,
+
+    /// How much to cut off from each side of the image before fitting it.
+    ///
+    /// The amounts are given relative to the image's natural size and can be
+    /// specified per side or with a dictionary like for a
+    /// [box's inset]($box.inset).
+    ///
+    /// ```example
+    /// #set page(width: 300pt, height: 50pt, margin: 10pt)
+    /// #image("tiger.jpg", height: 100%, crop: (x: 25%))
+    /// ```
+    pub crop: Sides<Option<Ratio>>,
 }
 
 #[scope]
@@ -113,6 +126,7 @@ impl ImageElem {
     /// #image.decode(changed)
     /// ```
     #[func(title = "Decode Image")]
+    #[allow(clippy::too_many_arguments)]
     pub fn decode(
         /// The call span of this function.
         span: Span,
@@ -133,6 +147,9 @@ impl ImageElem {
         /// How the image should adjust itself to a given area.
         #[named]
         fit: Option<ImageFit>,
+        /// How much to cut off from each side of the image.
+        #[named]
+        crop: Option<Sides<Option<Ratio>>>,
     ) -> StrResult<Content> {
         let mut elem = ImageElem::new(EcoString::new(), data);
         if let Some(format) = format {
@@ -150,8 +167,42 @@ impl ImageElem {
         if let Some(fit) = fit {
             elem.push_fit(fit);
         }
+        if let Some(crop) = crop {
+            elem.push_crop(crop);
+        }
         Ok(elem.pack().spanned(span))
     }
+
+    /// Reads metadata of an image file without displaying it.
+    ///
+    /// Returns a dictionary with the image's `format`, its natural `width`
+    /// and `height` (in pixels for raster images) and its pixel density
+    /// `dpi`, which is `{none}` if the image doesn't specify one.
+    ///
+    /// ```example
+    /// #let info = image.info("molecular.jpg")
+    /// The image is #info.width by
+    /// #info.height pixels large.
+    /// ```
+    #[func(title = "Image Info")]
+    pub fn info(
+        /// The engine.
+        engine: &mut Engine,
+        /// Path to an image file.
+        path: Spanned<EcoString>,
+    ) -> SourceResult<Dict> {
+        let Spanned { v: path, span } = path;
+        let id = span.resolve_path(&path).at(span)?;
+        let data = Readable::Bytes(engine.world.file(id).at(span)?);
+        let format = determine_format(&path, &data).at(span)?;
+        let image = Image::new(data.into(), format, None).at(span)?;
+        Ok(dict! {
+            "format" => format,
+            "width" => image.width(),
+            "height" => image.height(),
+            "dpi" => image.dpi(),
+        })
+    }
 }
 
 impl LayoutSingle for Packed<ImageElem> {
@@ -167,27 +218,7 @@ impl LayoutSingle for Packed<ImageElem> {
         let data = self.data();
         let format = match self.format(styles) {
             Smart::Custom(v) => v,
-            Smart::Auto => {
-                let ext = std::path::Path::new(self.path().as_str())
-                    .extension()
-                    .and_then(OsStr::to_str)
-                    .unwrap_or_default()
-                    .to_lowercase();
-
-                match ext.as_str() {
-                    "png" => ImageFormat::Raster(RasterFormat::Png),
-                    "jpg" | "jpeg" => ImageFormat::Raster(RasterFormat::Jpg),
-                    "gif" => ImageFormat::Raster(RasterFormat::Gif),
-                    "svg" | "svgz" => ImageFormat::Vector(VectorFormat::Svg),
-                    _ => match &data {
-                        Readable::Str(_) => ImageFormat::Vector(VectorFormat::Svg),
-                        Readable::Bytes(bytes) => match RasterFormat::detect(bytes) {
-                            Some(f) => ImageFormat::Raster(f),
-                            None => bail!(self.span(), "unknown image format"),
-                        },
-                    },
-                }
-            }
+            Smart::Auto => determine_format(self.path(), data).at(self.span())?,
         };
 
         let image = Image::with_fonts(
@@ -207,9 +238,18 @@ impl LayoutSingle for Packed<ImageElem> {
         let expand = sizing.as_ref().map(Smart::is_custom) | regions.expand;
         let region_ratio = region.x / region.y;
 
+        // Determine which fraction of the image remains visible after
+        // cropping.
+        let crop = self.crop(styles).map(|side| side.unwrap_or_default().get());
+        let visible =
+            Axes::new(1.0 - crop.left - crop.right, 1.0 - crop.top - crop.bottom);
+        if crop.iter().any(|&v| v < 0.0) || visible.x <= 0.0 || visible.y <= 0.0 {
+            bail!(self.span(), "cropping must leave part of the image visible");
+        }
+
         // Find out whether the image is wider or taller than the target size.
-        let pxw = image.width();
-        let pxh = image.height();
+        let pxw = image.width() * visible.x;
+        let pxh = image.height() * visible.y;
         let px_ratio = pxw / pxh;
         let wide = px_ratio > region_ratio;
 
@@ -249,9 +289,15 @@ impl LayoutSingle for Packed<ImageElem> {
 
         // First, place the image in a frame of exactly its size and then resize
         // the frame to the target size, center aligning the image in the
-        // process.
+        // process. A cropped image is scaled up and shifted so that only its
+        // visible part lies within the frame.
+        let full = Size::new(fitted.x / visible.x, fitted.y / visible.y);
+        let pos = Point::new(-full.x * crop.left, -full.y * crop.top);
         let mut frame = Frame::soft(fitted);
-        frame.push(Point::zero(), FrameItem::Image(image, fitted, self.span()));
+        frame.push(pos, FrameItem::Image(image, full, self.span()));
+        if full != fitted {
+            frame.clip(Path::rect(frame.size()));
+        }
         frame.resize(target, Axes::splat(FixedAlignment::Center));
 
         // Create a clipping group if only part of the image should be visible.
@@ -263,6 +309,31 @@ impl LayoutSingle for Packed<ImageElem> {
     }
 }
 
+/// Determine the format of an image from its path's extension or, failing
+/// that, from its data.
+fn determine_format(path: &str, data: &Readable) -> StrResult<ImageFormat> {
+    let ext = std::path::Path::new(path)
+        .extension()
+        .and_then(OsStr::to_str)
+        .unwrap_or_default()
+        .to_lowercase();
+
+    Ok(match ext.as_str() {
+        "png" => ImageFormat::Raster(RasterFormat::Png),
+        "jpg" | "jpeg" => ImageFormat::Raster(RasterFormat::Jpg),
+        "gif" => ImageFormat::Raster(RasterFormat::Gif),
+        "webp" => ImageFormat::Raster(RasterFormat::Webp),
+        "svg" | "svgz" => ImageFormat::Vector(VectorFormat::Svg),
+        _ => match data {
+            Readable::Str(_) => ImageFormat::Vector(VectorFormat::Svg),
+            Readable::Bytes(bytes) => match RasterFormat::detect(bytes) {
+                Some(f) => ImageFormat::Raster(f),
+                None => bail!("unknown image format"),
+            },
+        },
+    })
+}
+
 impl LocalName for Packed<ImageElem> {
     const KEY: &'static str = "figure";
 }
@@ -406,6 +477,57 @@ impl Image {
     pub fn kind(&self) -> &ImageKind {
         &self.0.kind
     }
+
+    /// Creates a copy of a raster image with every pixel's color transformed
+    /// by `f`.
+    ///
+    /// The copy is encoded in the same format as the original, so that e.g.
+    /// photos remain compact JPEGs. WebP images become PNGs because there is
+    /// no WebP encoder. Returns `None` for SVG images, whose colors can't be
+    /// transformed.
+    ///
+    /// The copy keeps the original's ICC profile, so its pixels are still
+    /// interpreted in the same color space.
+    #[comemo::memoize]
+    pub fn map_colors(&self, transform: ColorTransform) -> Option<Image> {
+        let ImageKind::Raster(raster) = &self.0.kind else { return None };
+
+        let mut buf = raster.dynamic().to_rgba8();
+        for pixel in buf.pixels_mut() {
+            let [r, g, b, a] = pixel.0;
+            let color = transform.apply(Color::from_u8(r, g, b, a));
+            pixel.0 = color.to_rgb().to_vec4_u8();
+        }
+
+        let (format, output, dynamic) = match raster.format() {
+            // JPEGs have no alpha channel. The quality is high enough to not
+            // add visible artifacts on top of the original's.
+            RasterFormat::Jpg => (
+                RasterFormat::Jpg,
+                image::ImageOutputFormat::Jpeg(90),
+                image::DynamicImage::ImageRgb8(
+                    image::DynamicImage::ImageRgba8(buf).to_rgb8(),
+                ),
+            ),
+            RasterFormat::Gif => (
+                RasterFormat::Gif,
+                image::ImageOutputFormat::Gif,
+                image::DynamicImage::ImageRgba8(buf),
+            ),
+            RasterFormat::Png | RasterFormat::Webp => (
+                RasterFormat::Png,
+                image::ImageOutputFormat::Png,
+                image::DynamicImage::ImageRgba8(buf),
+            ),
+        };
+
+        let mut data = vec![];
+        dynamic.write_to(&mut io::Cursor::new(&mut data), output).ok()?;
+        let icc = raster.icc().map(<[u8]>::to_vec);
+        let kind =
+            ImageKind::Raster(RasterImage::with_icc(data.into(), format, icc).ok()?);
+        Some(Self(Arc::new(LazyHash::new(Repr { kind, alt: self.0.alt.clone() }))))
+    }
 }
 
 impl Debug for Image {
@@ -456,3 +578,32 @@ cast! {
     v: RasterFormat => Self::Raster(v),
     v: VectorFormat => Self::Vector(v),
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_image_map_colors_keeps_format_and_icc() {
+        #[track_caller]
+        fn test(path: &str, format: RasterFormat) {
+            let data = Bytes::from_static(typst_dev_assets::get(path).unwrap());
+            let image = Image::new(data, format.into(), None).unwrap();
+            let mapped = image.map_colors(ColorTransform::Grayscale).unwrap();
+            assert_eq!(mapped.format(), ImageFormat::Raster(format));
+            assert_eq!(
+                (mapped.width(), mapped.height()),
+                (image.width(), image.height())
+            );
+            let (ImageKind::Raster(a), ImageKind::Raster(b)) =
+                (image.kind(), mapped.kind())
+            else {
+                panic!("expected raster images");
+            };
+            assert_eq!(a.icc(), b.icc());
+        }
+
+        test("images/tiger.jpg", RasterFormat::Jpg);
+        test("images/graph.png", RasterFormat::Png);
+    }
+}
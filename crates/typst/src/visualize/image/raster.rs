@@ -1,17 +1,21 @@
 use std::cmp::Ordering;
 use std::hash::{Hash, Hasher};
 use std::io;
-use std::sync::Arc;
+use std::sync::{Arc, OnceLock};
 
 use ecow::{eco_format, EcoString};
 use image::codecs::gif::GifDecoder;
 use image::codecs::jpeg::JpegDecoder;
 use image::codecs::png::PngDecoder;
+use image::codecs::webp::WebPDecoder;
 use image::io::Limits;
 use image::{guess_format, DynamicImage, ImageDecoder, ImageResult};
+use once_cell::sync::Lazy;
+use qcms::Profile;
 
 use crate::diag::{bail, StrResult};
 use crate::foundations::{Bytes, Cast};
+use crate::utils::Deferred;
 
 /// A decoded raster image.
 #[derive(Clone, Hash)]
@@ -21,48 +25,41 @@ pub struct RasterImage(Arc<Repr>);
 struct Repr {
     data: Bytes,
     format: RasterFormat,
-    dynamic: image::DynamicImage,
+    size: (u32, u32),
+    dynamic: Deferred<StrResult<DynamicImage>>,
     icc: Option<Vec<u8>>,
     dpi: Option<f64>,
+    srgb: OnceLock<Option<DynamicImage>>,
+    fallback: OnceLock<DynamicImage>,
 }
 
 impl RasterImage {
     /// Decode a raster image.
+    ///
+    /// Only the image's header is read right away. The pixels are decoded on
+    /// a background thread and only waited for when they are first accessed,
+    /// which typically happens during export.
     #[comemo::memoize]
     pub fn new(data: Bytes, format: RasterFormat) -> StrResult<RasterImage> {
-        fn decode_with<'a, T: ImageDecoder<'a>>(
-            decoder: ImageResult<T>,
-        ) -> ImageResult<(image::DynamicImage, Option<Vec<u8>>)> {
-            let mut decoder = decoder?;
-            let icc = decoder.icc_profile().filter(|icc| !icc.is_empty());
-            decoder.set_limits(Limits::default())?;
-            let dynamic = image::DynamicImage::from_decoder(decoder)?;
-            Ok((dynamic, icc))
-        }
-
-        let cursor = io::Cursor::new(&data);
-        let (mut dynamic, icc) = match format {
-            RasterFormat::Jpg => decode_with(JpegDecoder::new(cursor)),
-            RasterFormat::Png => decode_with(PngDecoder::new(cursor)),
-            RasterFormat::Gif => decode_with(GifDecoder::new(cursor)),
-        }
-        .map_err(format_image_error)?;
-
-        let exif = exif::Reader::new()
-            .read_from_container(&mut std::io::Cursor::new(&data))
-            .ok();
-
-        // Apply rotation from EXIF metadata.
-        if let Some(rotation) = exif.as_ref().and_then(exif_rotation) {
-            apply_rotation(&mut dynamic, rotation);
-        }
-
-        // Extract pixel density.
-        let dpi = determine_dpi(&data, exif.as_ref());
+        Ok(Self(Arc::new(Repr::load(data, format)?)))
+    }
 
-        Ok(Self(Arc::new(Repr { data, format, dynamic, icc, dpi })))
+    /// Decode a raster image, but interpret its colors with the given ICC
+    /// profile instead of the one embedded in the data.
+    ///
+    /// This is used for images that were re-encoded by an encoder that can't
+    /// embed ICC profiles.
+    pub fn with_icc(
+        data: Bytes,
+        format: RasterFormat,
+        icc: Option<Vec<u8>>,
+    ) -> StrResult<RasterImage> {
+        let mut repr = Repr::load(data, format)?;
+        repr.icc = icc;
+        Ok(Self(Arc::new(repr)))
     }
 
+    /// The raw image data.
     /// The raw image data.
     pub fn data(&self) -> &Bytes {
         &self.0.data
@@ -75,12 +72,12 @@ impl RasterImage {
 
     /// The image's pixel width.
     pub fn width(&self) -> u32 {
-        self.dynamic().width()
+        self.0.size.0
     }
 
     /// The image's pixel height.
     pub fn height(&self) -> u32 {
-        self.dynamic().height()
+        self.0.size.1
     }
 
     /// The image's pixel density in pixels per inch, if known.
@@ -88,22 +85,102 @@ impl RasterImage {
         self.0.dpi
     }
 
+    /// Access the underlying dynamic image or the error that occurred while
+    /// decoding its pixels.
+    ///
+    /// Blocks until the image is decoded.
+    pub fn decoded(&self) -> StrResult<&image::DynamicImage> {
+        self.0.dynamic.wait().as_ref().map_err(Clone::clone)
+    }
+
     /// Access the underlying dynamic image.
+    ///
+    /// Blocks until the image is decoded. If its pixels are corrupt, this is
+    /// a transparent image of the same size. Exporters can report this
+    /// beforehand with [`Frame::check_images`](crate::layout::Frame::check_images).
     pub fn dynamic(&self) -> &image::DynamicImage {
-        &self.0.dynamic
+        self.decoded().unwrap_or_else(|_| {
+            self.0.fallback.get_or_init(|| {
+                let (width, height) = self.0.size;
+                DynamicImage::new_rgba8(width, height)
+            })
+        })
     }
 
     /// Access the ICC profile, if any.
     pub fn icc(&self) -> Option<&[u8]> {
         self.0.icc.as_deref()
     }
+
+    /// Access the image with its colors converted from the embedded ICC
+    /// profile to sRGB.
+    ///
+    /// This is useful for consumers that cannot handle ICC profiles
+    /// themselves. If there is no profile or it cannot be applied, this is
+    /// the same as [`dynamic`](Self::dynamic).
+    pub fn srgb(&self) -> &image::DynamicImage {
+        self.0
+            .srgb
+            .get_or_init(|| to_srgb(self.dynamic(), self.icc()?))
+            .as_ref()
+            .unwrap_or_else(|| self.dynamic())
+    }
+}
+
+impl Repr {
+    /// Read the image's header and start decoding its pixels.
+    fn load(data: Bytes, format: RasterFormat) -> StrResult<Self> {
+        let Header { size: (width, height), icc } =
+            read_header(&data, format).map_err(format_image_error)?;
+
+        let exif = exif::Reader::new()
+            .read_from_container(&mut std::io::Cursor::new(&data))
+            .ok();
+
+        // Rotations by 90 or 270 degrees swap the image's dimensions.
+        let rotation = exif.as_ref().and_then(exif_rotation);
+        let size = match rotation {
+            Some(5..=8) => (height, width),
+            _ => (width, height),
+        };
+
+        // Extract pixel density.
+        let dpi = determine_dpi(&data, exif.as_ref());
+
+        // The pixel data can still turn out to be corrupt even though the
+        // header was valid. This is reported when the image is waited for.
+        let dynamic = {
+            let data = data.clone();
+            Deferred::new(move || {
+                let mut dynamic =
+                    decode_pixels(&data, format).map_err(format_image_error)?;
+                if let Some(rotation) = rotation {
+                    apply_rotation(&mut dynamic, rotation);
+                }
+                Ok(dynamic)
+            })
+        };
+
+        Ok(Self {
+            data,
+            format,
+            size,
+            dynamic,
+            icc,
+            dpi,
+            srgb: OnceLock::new(),
+            fallback: OnceLock::new(),
+        })
+    }
 }
 
 impl Hash for Repr {
     fn hash<H: Hasher>(&self, state: &mut H) {
-        // The image is fully defined by data and format.
+        // The image is fully defined by data, format, and ICC profile, which
+        // is usually read from the data.
         self.data.hash(state);
         self.format.hash(state);
+        self.icc.hash(state);
     }
 }
 
@@ -116,6 +193,8 @@ pub enum RasterFormat {
     Jpg,
     /// Raster format that is typically used for short animated clips.
     Gif,
+    /// Modern raster format with lossy and lossless compression.
+    Webp,
 }
 
 impl RasterFormat {
@@ -131,6 +210,7 @@ impl From<RasterFormat> for image::ImageFormat {
             RasterFormat::Png => image::ImageFormat::Png,
             RasterFormat::Jpg => image::ImageFormat::Jpeg,
             RasterFormat::Gif => image::ImageFormat::Gif,
+            RasterFormat::Webp => image::ImageFormat::WebP,
         }
     }
 }
@@ -143,11 +223,82 @@ impl TryFrom<image::ImageFormat> for RasterFormat {
             image::ImageFormat::Png => RasterFormat::Png,
             image::ImageFormat::Jpeg => RasterFormat::Jpg,
             image::ImageFormat::Gif => RasterFormat::Gif,
+            image::ImageFormat::WebP => RasterFormat::Webp,
             _ => bail!("Format not yet supported."),
         })
     }
 }
 
+/// The information from an image's header.
+struct Header {
+    /// The pixel dimensions, before applying any EXIF rotation.
+    size: (u32, u32),
+    /// The embedded ICC profile, if any.
+    icc: Option<Vec<u8>>,
+}
+
+/// Read the dimensions and ICC profile of an image without decoding it.
+fn read_header(data: &[u8], format: RasterFormat) -> ImageResult<Header> {
+    fn read_with<'a, T: ImageDecoder<'a>>(
+        decoder: ImageResult<T>,
+    ) -> ImageResult<Header> {
+        let mut decoder = decoder?;
+        let icc = decoder.icc_profile().filter(|icc| !icc.is_empty());
+        decoder.set_limits(Limits::default())?;
+        Ok(Header { size: decoder.dimensions(), icc })
+    }
+
+    let cursor = io::Cursor::new(data);
+    match format {
+        RasterFormat::Jpg => read_with(JpegDecoder::new(cursor)),
+        RasterFormat::Png => read_with(PngDecoder::new(cursor)),
+        RasterFormat::Gif => read_with(GifDecoder::new(cursor)),
+        RasterFormat::Webp => read_with(WebPDecoder::new(cursor)),
+    }
+}
+
+/// Decode the pixels of an image.
+fn decode_pixels(data: &[u8], format: RasterFormat) -> ImageResult<DynamicImage> {
+    fn decode_with<'a, T: ImageDecoder<'a>>(
+        decoder: ImageResult<T>,
+    ) -> ImageResult<DynamicImage> {
+        let mut decoder = decoder?;
+        decoder.set_limits(Limits::default())?;
+        DynamicImage::from_decoder(decoder)
+    }
+
+    let cursor = io::Cursor::new(data);
+    match format {
+        RasterFormat::Jpg => decode_with(JpegDecoder::new(cursor)),
+        RasterFormat::Png => decode_with(PngDecoder::new(cursor)),
+        RasterFormat::Gif => decode_with(GifDecoder::new(cursor)),
+        RasterFormat::Webp => decode_with(WebPDecoder::new(cursor)),
+    }
+}
+
+/// The sRGB profile images are converted into.
+static SRGB_PROFILE: Lazy<Box<Profile>> = Lazy::new(|| {
+    let mut out = Profile::new_sRGB();
+    out.precache_output_transform();
+    out
+});
+
+/// Convert an image with an ICC profile to sRGB.
+///
+/// Returns `None` if the profile is invalid or doesn't describe RGB data.
+fn to_srgb(image: &DynamicImage, icc: &[u8]) -> Option<DynamicImage> {
+    let profile = Profile::new_from_slice(icc, false)?;
+    let transform = qcms::Transform::new(
+        &profile,
+        &SRGB_PROFILE,
+        qcms::DataType::RGBA8,
+        qcms::Intent::Perceptual,
+    )?;
+    let mut buf = image.to_rgba8();
+    transform.apply(&mut buf);
+    Some(DynamicImage::ImageRgba8(buf))
+}
+
 /// Try to get the rotation from the EXIF metadata.
 fn exif_rotation(exif: &exif::Exif) -> Option<u32> {
     exif.get_field(exif::Tag::Orientation, exif::In::PRIMARY)?
@@ -284,4 +435,22 @@ mod tests {
         test("images/tiger.jpg", RasterFormat::Jpg, 72.0);
         test("images/graph.png", RasterFormat::Png, 144.0);
     }
+
+    #[test]
+    fn test_image_corrupt_pixels() {
+        // A 2x2 PNG with a valid header, but an invalid deflate stream.
+        static DATA: [u8; 63] = [
+            0x89, 0x50, 0x4e, 0x47, 0x0d, 0x0a, 0x1a, 0x0a, 0x00, 0x00, 0x00, 0x0d, 0x49,
+            0x48, 0x44, 0x52, 0x00, 0x00, 0x00, 0x02, 0x00, 0x00, 0x00, 0x02, 0x08, 0x06,
+            0x00, 0x00, 0x00, 0x72, 0xb6, 0x0d, 0x24, 0x00, 0x00, 0x00, 0x06, 0x49, 0x44,
+            0x41, 0x54, 0x78, 0x9c, 0xff, 0xff, 0xff, 0xff, 0x1d, 0xca, 0x7c, 0x9e, 0x00,
+            0x00, 0x00, 0x00, 0x49, 0x45, 0x4e, 0x44, 0xae, 0x42, 0x60, 0x82,
+        ];
+
+        let image =
+            RasterImage::new(Bytes::from_static(&DATA), RasterFormat::Png).unwrap();
+        assert_eq!((image.width(), image.height()), (2, 2));
+        assert!(image.decoded().is_err());
+        assert_eq!(image.dynamic().to_rgba8().dimensions(), (2, 2));
+    }
 }
@@ -11,7 +11,7 @@ use usvg::{ImageHrefResolver, Node, PostProcessingSteps, TreeParsing, TreePostPr
 use crate::diag::{format_xml_like_error, StrResult};
 use crate::foundations::Bytes;
 use crate::layout::Axes;
-use crate::text::{FontVariant, FontWeight};
+use crate::text::{FontBook, FontFlags, FontVariant, FontWeight};
 use crate::visualize::Image;
 use crate::World;
 
@@ -193,10 +193,14 @@ fn load_svg_fonts(
                     // and the current document font families.
                     let mut like = None;
                     for family in span.font.families.iter().chain(families) {
-                        let Some(id) = book.select(&family.to_lowercase(), variant)
+                        let Some(family) =
+                            resolve_generic_family(book, &family.to_lowercase())
                         else {
                             continue;
                         };
+                        let Some(id) = book.select(&family, variant) else {
+                            continue;
+                        };
                         let Some(info) = book.info(id) else { continue };
                         like.get_or_insert(info);
 
@@ -222,6 +226,27 @@ fn load_svg_fonts(
     (fontdb, hasher.finish128().as_u128())
 }
 
+/// Map a generic CSS font family to a concrete family in the font book.
+///
+/// Generic serif and sans-serif families are covered by the document's
+/// families, but plots often use `monospace` for tick labels and code. For it,
+/// we prefer the monospace font that Typst ships with and otherwise take the
+/// first monospaced family that is available. If there is none, the family is
+/// skipped in favor of the remaining ones.
+fn resolve_generic_family(book: &FontBook, family: &str) -> Option<EcoString> {
+    match family {
+        "monospace" if book.contains_family("dejavu sans mono") => {
+            Some("dejavu sans mono".into())
+        }
+        "monospace" => book.families().find_map(|(family, mut infos)| {
+            infos
+                .any(|info| info.flags.contains(FontFlags::MONOSPACE))
+                .then(|| family.to_lowercase().into())
+        }),
+        _ => Some(family.into()),
+    }
+}
+
 /// Search for all font families referenced by an SVG.
 fn traverse_svg<F>(node: &mut usvg::Node, f: &mut F)
 where
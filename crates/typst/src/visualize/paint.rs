@@ -3,7 +3,7 @@ use std::fmt::{self, Debug, Formatter};
 use ecow::EcoString;
 
 use crate::foundations::{cast, Repr, Smart};
-use crate::visualize::{Color, Gradient, Pattern, RelativeTo};
+use crate::visualize::{Color, ColorTransform, Gradient, Pattern, RelativeTo};
 
 /// How a fill or stroke should be painted.
 #[derive(Clone, Eq, PartialEq, Hash)]
@@ -34,6 +34,19 @@ impl Paint {
         }
     }
 
+    /// Applies a color transformation to all colors of this paint.
+    pub fn map_colors(&self, transform: ColorTransform) -> Self {
+        match self {
+            Self::Solid(color) => Self::Solid(transform.apply(*color)),
+            Self::Gradient(gradient) => {
+                Self::Gradient(gradient.clone().map_colors(transform))
+            }
+            Self::Pattern(pattern) => {
+                Self::Pattern(pattern.clone().map_colors(transform))
+            }
+        }
+    }
+
     /// Turns this paint into a paint for a text decoration.
     ///
     /// If this paint is a gradient, it will be converted to a gradient with
@@ -9,7 +9,7 @@ use crate::foundations::{func, repr, scope, ty, Content, Smart, StyleChain};
 use crate::layout::{Abs, Axes, Frame, LayoutMultiple, Length, Regions, Size};
 use crate::syntax::{Span, Spanned};
 use crate::utils::{LazyHash, Numeric};
-use crate::visualize::RelativeTo;
+use crate::visualize::{ColorTransform, RelativeTo};
 use crate::World;
 
 /// A repeating pattern fill.
@@ -230,6 +230,13 @@ impl Pattern {
         self
     }
 
+    /// Clones this pattern, but with a color transformation applied to all
+    /// colors in its frame.
+    pub fn map_colors(mut self, transform: ColorTransform) -> Self {
+        Arc::make_mut(&mut self.0).frame.map_colors(transform);
+        self
+    }
+
     /// Return the frame of the pattern.
     pub fn frame(&self) -> &Frame {
         &self.0.frame
@@ -355,6 +355,15 @@
 #test((2, 1, 3, -10, -5, 8, 6, -7, 2).sorted(key: x => x), (-10, -7, -5, 1, 2, 2, 3, 6, 8))
 #test((2, 1, 3, -10, -5, 8, 6, -7, 2).sorted(key: x => x * x), (1, 2, 2, 3, -5, 6, -7, 8, -10))
 
+--- array-sorted-key-stable ---
+// Items with equal keys keep their order.
+#let pairs = ((1, "b"), (0, "c"), (1, "a"), (0, "d"))
+#test(pairs.sorted(key: p => p.first()).map(p => p.last()), ("c", "d", "b", "a"))
+
+--- array-sorted-key-error ---
+// Error: 28-33 cannot add string and integer
+#(1, "a").sorted(key: x => x + 1)
+
 --- array-sorted-key-function-positional-1 ---
 // Error: 12-18 unexpected argument
 #().sorted(x => x)
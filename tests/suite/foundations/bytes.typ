@@ -7,6 +7,22 @@
 #test(str(data.slice(1, 4)), "PNG")
 #test(repr(data), "bytes(232243)")
 
+--- bytes-at ---
+#let data = bytes((10, 20, 30))
+#test(data.at(0), 10)
+#test(data.at(-1), 30)
+#test(data.at(3, default: none), none)
+#test(data.slice(-2), bytes((20, 30)))
+
+--- bytes-at-out-of-bounds ---
+// Error: 2-24 byte index out of bounds (index: 3, len: 3) and no default value was specified
+#bytes((1, 2, 3)).at(3)
+
+--- bytes-iter ---
+#let sum = 0
+#for byte in bytes((1, 2, 3)) { sum += byte }
+#test(sum, 6)
+
 --- bytes-string-conversion ---
 #test(str(bytes(range(0x41, 0x50))), "ABCDEFGHIJKLMNO")
 
@@ -29,3 +45,11 @@
 --- bytes-bad-conversion-from-dict ---
 // Error: 8-14 expected string, array, or bytes, found dictionary
 #bytes((a: 1))
+
+--- bytes-bad-conversion-from-large-int ---
+// Error: 8-16 number must be between 0 and 255
+#bytes((1, 256))
+
+--- bytes-bad-utf8 ---
+// Error: 6-19 bytes are not valid utf-8
+#str(bytes((255,)))
@@ -259,3 +259,17 @@
 --- calc-max-uncomparable ---
 // Error: 16-19 cannot compare 1pt with 1em
 #calc.max(1em, 1pt)
+
+--- calc-format-number ---
+#test(calc.format-number(1234567), "1234567")
+#test(calc.format-number(1234567, thousands: ","), "1,234,567")
+#test(calc.format-number(123, thousands: ","), "123")
+#test(calc.format-number(-1234.5, thousands: " "), "−1 234.5")
+#test(calc.format-number(3, decimals: 2), "3.00")
+#test(calc.format-number(2.675, decimals: 1), "2.7")
+#test(calc.format-number(0.5, decimals: 3, point: ","), "0,500")
+#test(calc.format-number(-0.001, decimals: 2), "0.00")
+
+--- calc-format-number-infinite ---
+// Error: 21-29 cannot format a number that is not finite
+#calc.format-number(calc.inf)
@@ -0,0 +1,22 @@
+--- catch-success ---
+#test(catch(() => 1 + 2), 3)
+#test(catch(() => "hi", default: "fallback"), "hi")
+
+--- catch-default ---
+#test(catch(() => panic("oh no")), none)
+#test(catch(() => 1 + "a", default: 0), 0)
+#test(catch(() => read("does-not-exist.txt"), default: ""), "")
+
+--- catch-default-func ---
+#test(catch(() => panic("oh no"), default: msg => msg), "panicked with: \"oh no\"")
+#test(catch(() => assert(false, message: "bad"), default: upper), "ASSERTION FAILED: BAD")
+
+--- catch-error-in-default ---
+// Error: 37-44 panicked
+#catch(() => panic(), default: _ => panic())
+
+--- catch-any-error ---
+// Not only user-raised errors are caught.
+#test(catch(() => undefined, default: msg => msg), "unknown variable: undefined")
+#test(catch(() => eval("1 +"), default: "syntax"), "syntax")
+#test(catch(() => (1, 2).at(5), default: 0), 0)
@@ -91,3 +91,24 @@
 --- datetime-display-insufficient-information ---
 // Error: 2-36 failed to format datetime (insufficient information)
 #datetime.today().display("[hour]")
+
+--- datetime-compare ---
+#let a = datetime(year: 2023, month: 7, day: 12)
+#let b = datetime(year: 2024, month: 1, day: 1)
+#test(a < b, true)
+#test(a == datetime(year: 2023, month: 7, day: 12), true)
+#test(
+  datetime(hour: 9, minute: 30, second: 0) > datetime(hour: 9, minute: 0, second: 0),
+  true,
+)
+#test((b, a).sorted(), (a, b))
+
+--- datetime-compare-different-kinds ---
+// Error: 3-84 cannot compare date and time
+#(datetime(year: 2023, month: 7, day: 12) < datetime(hour: 1, minute: 0, second: 0))
+
+--- datetime-today ---
+// The test world fixes the current date for reproducibility.
+#test(datetime.today(), datetime(year: 1970, month: 1, day: 1))
+#test(datetime.today().display(), "1970-01-01")
+#test(datetime.today() + duration(days: 31), datetime(year: 1970, month: 2, day: 1))
@@ -30,6 +30,24 @@
 // Error: 6-12 invalid integer: nope
 #int("nope")
 
+--- int-constructor-float-truncates ---
+#test(int(2.7), 2)
+#test(int(-2.7), -2)
+#test(int(-9223372036854775808.0), -9223372036854775807 - 1)
+#test(int(9223372036854774784.0), 9223372036854774784)
+
+--- int-constructor-float-nan ---
+// Error: 6-14 cannot convert NaN to an integer
+#int(calc.nan)
+
+--- int-constructor-float-too-large ---
+// Error: 6-14 number too large
+#int(calc.inf)
+
+--- int-constructor-float-two-to-the-63 ---
+// Error: 6-27 number too large
+#int(9223372036854775808.0)
+
 --- int-signum ---
 // Test int `signum()`
 #test(int(0).signum(), 0)
@@ -172,6 +172,16 @@
   (start: 4, end: 8, text: "time", captures: ()),
 )
 
+--- string-match-optional-capture ---
+// Test that skipped capturing groups yield `none`.
+#let version = regex("(\d+)\.(\d+)(?:\.(\d+))?")
+#test("v1.2".match(version).captures, ("1", "2", none))
+#test("v1.2.3".match(version).captures, ("1", "2", "3"))
+#test(
+  "1.0 and 2.5.1".matches(version).map(m => (m.start, m.end)),
+  ((0, 3), (8, 13)),
+)
+
 --- string-matches ---
 // Test the `matches` method.
 #test("Hello there".matches("\d"), ())
@@ -310,6 +320,30 @@
 #test("abc".rev(), "cba")
 #test("ax̂e".rev(), "ex̂a")
 
+--- string-pad ---
+// Test the `pad` method.
+#test("42".pad(5, fill: "0", at: start), "00042")
+#test("ab".pad(4), "ab  ")
+#test("abc".pad(2), "abc")
+#test("🏳️‍🌈".pad(3, fill: "-"), "🏳️‍🌈--")
+#test("".pad(3, fill: "é", at: end), "ééé")
+
+--- string-pad-bad-fill ---
+// Error: 2-24 fill must be exactly one grapheme cluster
+#"a".pad(3, fill: "ab")
+
+--- string-normalize ---
+// Test the `normalize` method.
+#test("e\u{0301}".normalize(), "é")
+#test("é".normalize(form: "nfd"), "e\u{0301}")
+#test("ﬁ".normalize(form: "nfc"), "ﬁ")
+#test("ﬁ".normalize(form: "nfkc"), "fi")
+#test("Å".normalize(form: "nfkd").codepoints().len(), 2)
+
+--- string-normalize-bad-form ---
+// Error: 22-27 expected "nfc", "nfd", "nfkc", or "nfkd"
+#"a".normalize(form: "nfx")
+
 --- string-unclosed ---
 // Error: 2-2:1 unclosed string
 #"hello\"
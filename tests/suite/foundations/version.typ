@@ -45,3 +45,8 @@
 --- version-type ---
 // Test the type of `sys.version`
 #test(type(sys.version), version)
+
+--- sys-features ---
+#test(type(sys.features), dictionary)
+#test(sys.features.at("stats", default: false), true)
+#test(sys.features.at("no-such-feature", default: false), false)
@@ -37,3 +37,22 @@
 
 // Error: 10-25 selector matches multiple elements
 #context locate(heading)
+
+--- locate-page-geometry ---
+#set page(height: 20pt, margin: (x: 10pt, top: 5pt, bottom: 3pt))
+#context {
+  let page = here().page-geometry()
+  test(page.width, 120pt)
+  test(page.height, 20pt)
+  test(page.margin, (top: 5pt, right: 10pt, bottom: 3pt, left: 10pt))
+  test(page.columns, 1)
+}
+
+--- locate-page-geometry-bleed-columns ---
+#set page(height: 20pt, columns: 2, bleed: 3pt)
+#context {
+  let page = here().page-geometry()
+  test(page.width, 120pt)
+  test(page.height, 20pt)
+  test(page.columns, 2)
+}
@@ -49,9 +49,10 @@ Was: #context {
 
 --- state-no-convergence ---
 // Make sure that a warning is produced if the layout fails to converge.
-// Warning: layout did not converge within 5 attempts
-// Hint: check if any states or queries are updating themselves
 #let s = state("s", 1)
+// Warning: 10-33 layout did not converge within 5 attempts
+// Hint: 10-33 check if any states or queries are updating themselves
+// Hint: 10-33 this element still changed in the last attempt
 #context s.update(s.final() + 1)
 #context s.get()
 
@@ -0,0 +1,56 @@
+--- stats-empty ---
+#context {
+  let s = stats()
+  assert.eq(s.words, 0)
+  assert.eq(s.characters, 0)
+  assert.eq(s.pages, 1)
+}
+
+--- stats-sections ---
+= Intro <intro>
+One two three.
+
+= Next <next>
+Four five.
+
+#context {
+  let s = stats()
+  assert.eq(s.words, 7)
+  assert.eq(s.characters, 30)
+  assert.eq(s.pages, 1)
+
+  let intro = stats(query(<intro>).first().location())
+  assert.eq(intro.words, 4)
+  assert.eq(intro.characters, 17)
+
+  let next = stats(query(<next>).first().location())
+  assert.eq(next.words, 3)
+  assert.eq(next.characters, 13)
+}
+
+--- stats-split-words ---
+#set page(height: auto)
+Un#strong[bold]ed and #text(red)[col]ored words.
+
+#context {
+  let s = stats()
+  assert.eq(s.words, 4)
+  assert.eq(s.characters, 24)
+}
+
+--- stats-section-other-element ---
+#metadata(none) <a>
+One two.
+#metadata(none) <b>
+Three.
+
+#context {
+  assert.eq(stats(query(<a>).first().location()).words, 2)
+  assert.eq(stats(query(<b>).first().location()).words, 1)
+}
+
+--- stats-no-context ---
+// Error: 2-9 can only be used when context is known
+// Hint: 2-9 try wrapping this in a `context` expression
+// Hint: 2-9 the `context` expression should wrap everything that depends on this function
+#stats()
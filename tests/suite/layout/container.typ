@@ -176,3 +176,24 @@ First!
 // Test box in 100% width block.
 #block(width: 100%, fill: red, box("a box"))
 #block(width: 100%, fill: red, [#box("a box") #box()])
+
+--- block-fractional-height ---
+#set page(height: 20pt, margin: 0pt)
+#set block(spacing: 0pt)
+#block(rest: 1fr) <a>
+#block(rest: 3fr) <b>
+#context {
+  test(locate(<a>).position().y, 0pt)
+  test(locate(<b>).position().y, 5pt)
+}
+
+--- block-sticky ---
+#set page(height: 20pt, margin: 0pt)
+#set block(spacing: 0pt)
+#block(height: 5pt)
+#block(height: 5pt, sticky: true) <a>
+#block(height: 12pt, breakable: false) <b>
+#context {
+  test(locate(<a>).page(), 2)
+  test(locate(<b>).page(), 2)
+}
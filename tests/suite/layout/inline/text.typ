@@ -5,6 +5,13 @@
 #text(kerning: true)[Tq] \
 #text(kerning: false)[Tq]
 
+--- text-kerning-partial-styling ---
+// Test that styling part of a word keeps kerning and ligatures.
+#set par(shape-across-styles: true)
+#context test(measure[Tq].width, measure[T#text(red)[q]].width)
+#context test(measure[fi].width, measure[f#text(red)[i]].width)
+#context test(measure[AVA].width, measure[A#underline[V]A].width)
+
 --- text-alternates-and-stylistic-sets ---
 // Test alternates and stylistic sets.
 #set text(font: "IBM Plex Serif")
@@ -15,6 +22,17 @@ a vs #text(alternates: true)[a] \
 // Test ligatures.
 fi vs. #text(ligatures: false)[No fi]
 
+--- text-ligatures-auto ---
+// Test that ligatures are suppressed across style boundaries and in
+// letter-spaced text when set to `auto`.
+#set text(ligatures: auto)
+#set par(shape-across-styles: true)
+#let plain = text(ligatures: false)[fi]
+#context test(measure[f#text(red)[i]].width, measure(plain).width)
+#set text(tracking: 1pt)
+#context test(measure[fi].width, measure(plain).width)
+#context assert.ne(measure(text(ligatures: true)[fi]).width, measure(plain).width)
+
 --- text-number-type ---
 // Test number type.
 #set text(number-type: "old-style")
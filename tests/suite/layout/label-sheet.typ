@@ -0,0 +1,33 @@
+--- label-sheet-infinite-height ---
+#set page(height: auto)
+// Error: 2-33 cannot divide an infinite height into label rows
+#label-sheet(rows: 2, i => [#i])
+
+--- label-sheet-count-zero ---
+// Produces no labels and thus no visible output.
+#label-sheet(count: 0, i => panic())
+
+--- label-sheet ---
+#set page(height: 80pt, margin: 10pt)
+#label-sheet(
+  columns: 2,
+  rows: 3,
+  gutter: 4pt,
+  skip: 1,
+  count: 4,
+  i => rect(width: 100%, height: 100%, inset: 2pt)[#(i + 1)],
+)
+
+--- label-sheet-offset ---
+// The offset is part of the sheet's size.
+#set page(width: auto, height: auto, margin: 5pt)
+#label-sheet(
+  columns: 2,
+  rows: 2,
+  width: 30pt,
+  height: 20pt,
+  gutter: 2pt,
+  dx: 6pt,
+  dy: 4pt,
+  i => rect(width: 100%, height: 100%, inset: 2pt)[#(i + 1)],
+)
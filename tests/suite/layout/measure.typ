@@ -7,3 +7,45 @@
 }
 #text(10pt, f(6pt, 8pt))
 #text(20pt, f(13pt, 14pt))
+
+--- measure-width ---
+// Test measuring in a constrained width.
+#context {
+  let body = lorem(20)
+  let free = measure(body)
+  let narrow = measure(width: 3cm, body)
+  assert(narrow.width <= 3cm)
+  assert(narrow.height > free.height)
+  assert(not narrow.breaks)
+}
+
+--- measure-baseline ---
+// Test the baseline information of measured content.
+#context {
+  let m = measure(text(top-edge: 8pt)[Hello])
+  assert.eq(m.baseline, 8pt)
+  assert.eq(m.ascent, 8pt)
+  assert.eq(m.descent, m.height - 8pt)
+}
+
+#context {
+  let m = measure(text(bottom-edge: "descender", box[Hello]))
+  assert(m.descent > 0pt)
+  assert.eq(m.ascent + m.descent, m.height)
+}
+
+#context {
+  // The baseline is the one of the first line.
+  let m = measure(text(top-edge: 8pt)[Hello \ World])
+  assert.eq(m.baseline, 8pt)
+  assert(m.descent > 8pt)
+}
+
+--- measure-breaks ---
+// Test detecting whether content breaks across regions.
+#context {
+  let m = measure(width: 100pt, height: 1cm, lorem(100))
+  assert(m.breaks)
+  assert(m.height <= 1cm)
+  assert(not measure(lorem(100)).breaks)
+}
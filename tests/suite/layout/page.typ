@@ -68,6 +68,11 @@
 #text(15pt, font: "Roboto", fill: white, smallcaps[Typst])
 #page(width: 40pt, fill: none, margin: (top: 10pt, rest: auto))[Hi]
 
+--- page-bleed-marks ---
+// Test that the fill covers the bleed and that crop marks surround it.
+#set page(width: 60pt, height: 40pt, margin: 5pt, fill: eastern, bleed: 3mm, marks: true)
+#text(fill: white)[Hi]
+
 --- page-margin-uniform ---
 // Set all margins at once.
 #[
@@ -238,3 +243,10 @@ Look, ma, no page numbers!
 
 #set page(header: auto, footer: auto)
 Default page numbers now.
+
+--- page-transition ---
+#set page(transition: "dissolve", duration: duration(seconds: 5))
+
+--- page-transition-bad ---
+// Error: 23-29 expected "split", "blinds", "box", "wipe", "dissolve", "glitter", "fly", "push", "cover", "uncover", "fade", or none
+#set page(transition: "spin")
@@ -42,3 +42,24 @@ A#box(width: 1fr, repeat(rect(width: 6em, height: 0.7em)))B
 // Error: 2:2-2:13 repeat with no size restrictions
 #set page(width: auto)
 #repeat(".")
+
+--- repeat-gap-fraction ---
+// Error: 14-17 expected length, found fraction
+#repeat(gap: 1fr)[.]
+
+--- repeat-gap ---
+// Test a fixed gap between the instances.
+#box(width: 1fr, repeat(gap: 0.5em)[.])
+
+#box(width: 1fr, repeat(gap: -0.5em, rect(width: 1em, height: 0.5em, fill: aqua)))
+
+--- repeat-no-justify ---
+// Test instances that are packed tightly and aligned.
+#box(width: 1fr, repeat(justify: false)[. ])
+
+#set align(right)
+#box(width: 1fr, repeat(justify: false, gap: 2pt, rect(width: 1em, height: 0.5em)))
+
+--- repeat-gap-too-small ---
+// Error: 18-66 gap must be greater than the negated width of the body
+#box(width: 1fr, repeat(gap: -1em, rect(width: 1em, height: 1em)))
@@ -25,3 +25,16 @@
 // Test error numbering with dictionary rows.
 // Error: 6-28 failed to parse CSV (found 3 instead of 2 fields in line 3)
 #csv("/assets/data/bad.csv", row-type: dictionary)
+
+--- csv-decode-delimiter ---
+// Test decoding CSV data from a string with a custom delimiter.
+#let data = csv.decode("Name;Age\nDebby;12\nFluffy;3", delimiter: ";")
+#test(data, (("Name", "Age"), ("Debby", "12"), ("Fluffy", "3")))
+
+--- csv-decode-row-type-dict ---
+#let data = csv.decode(bytes("a,b\n1,2"), row-type: dictionary)
+#test(data, ((a: "1", b: "2"),))
+
+--- csv-delimiter-too-long ---
+// Error: 31-35 delimiter must be a single character
+#csv.decode("a,b", delimiter: "ab")
@@ -14,3 +14,9 @@
 // but not overflow
 #let bignum = json("/assets/data/big-number.json")
 #bignum
+
+--- json-decode ---
+// Test decoding JSON data from a string.
+#let data = json.decode("{\"name\": \"Debby\", \"tags\": [1, 2.5, null, true]}")
+#test(data, (name: "Debby", tags: (1, 2.5, none, true)))
+#test(json.decode(json.encode(data)), data)
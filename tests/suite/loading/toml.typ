@@ -39,3 +39,9 @@
 --- toml-invalid ---
 // Error: 7-30 failed to parse TOML (expected `.`, `=` at line 1 column 16)
 #toml("/assets/data/bad.toml")
+
+--- toml-decode ---
+// Test decoding TOML data from a string.
+#let data = toml.decode("title = \"Zoo\"\n[animal]\nname = \"Debby\"\nage = 12\n")
+#test(data, (title: "Zoo", animal: (name: "Debby", age: 12)))
+#test(toml.decode(toml.encode(data)), data)
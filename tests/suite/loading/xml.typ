@@ -26,3 +26,12 @@
 --- xml-invalid ---
 // Error: 6-28 failed to parse XML (found closing tag 'data' instead of 'hello' in line 3)
 #xml("/assets/data/bad.xml")
+
+--- xml-decode ---
+// Test decoding XML data from a string.
+#let data = xml.decode("<zoo size=\"1\"><animal>Debby</animal></zoo>")
+#test(data, ((
+  tag: "zoo",
+  attrs: (size: "1"),
+  children: ((tag: "animal", attrs: (:), children: ("Debby",)),),
+),))
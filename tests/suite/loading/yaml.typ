@@ -15,3 +15,9 @@
 --- yaml-invalid ---
 // Error: 7-30 failed to parse YAML (did not find expected ',' or ']' at line 2 column 1, while parsing a flow sequence at line 1 column 18)
 #yaml("/assets/data/bad.yaml")
+
+--- yaml-decode ---
+// Test decoding YAML data from a string.
+#let data = yaml.decode("name: Debby\ntags:\n  - 1\n  - two\n")
+#test(data, (name: "Debby", tags: (1, "two")))
+#test(yaml.decode(yaml.encode(data)), data)
@@ -9,6 +9,14 @@ What's up?
 // This, too.
 #set document(author: ("A", "B"), date: datetime.today())
 
+--- document-set-print-preferences ---
+// This, too, has no visible output.
+#set document(duplex: "flip-long-edge", print-scaling: false, copies: 2)
+
+--- document-duplex-bad ---
+// Error: 23-29 expected "simplex", "flip-short-edge", "flip-long-edge", or none
+#set document(duplex: "both")
+
 --- document-date-bad ---
 // Error: 21-28 expected datetime, none, or auto, found string
 #set document(date: "today")
@@ -130,6 +130,17 @@ Definition #footnote[Hi]<fn>
 Real #footnote[...]<fn> \
 Ref @fn
 
+--- footnote-per-page ---
+// Test restarting the numbering on every page.
+#set page(height: 80pt)
+#set footnote(numbering: "*", per-page: true)
+A #footnote[One]
+B #footnote[Two] <two>
+#pagebreak()
+C #footnote[Three] <three>
+#context test(counter(footnote).at(<two>), (2,))
+#context test(counter(footnote).at(<three>), (1,))
+
 --- footnote-ref-call ---
 // Footnote call with label
 #footnote(<fn>)
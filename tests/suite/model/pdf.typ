@@ -0,0 +1,29 @@
+// Test PDF-specific functionality.
+
+--- pdf-embed ---
+#pdf.embed("/assets/data/zoo.csv")
+#pdf.embed(
+  "/assets/data/zoo.json",
+  description: "Information about the zoo",
+  mime-type: "application/json",
+)
+
+--- pdf-embed-query ---
+#pdf.embed("/assets/data/zoo.csv", description: "Animals") <zoo>
+#context test(query(<zoo>).first().description, "Animals")
+
+--- pdf-embed-missing ---
+// Error: 12-22 file not found (searched at tests/suite/model/nope.csv)
+#pdf.embed("nope.csv")
+
+--- pdf-embed-relationship ---
+#pdf.embed("/assets/data/zoo.csv", relationship: "data")
+#pdf.embed("/assets/data/zoo.json", relationship: "supplement")
+
+--- pdf-embed-duplicate ---
+#pdf.embed("/assets/data/zoo.csv")
+#pdf.embed("/assets/data/zoo.csv")
+
+--- pdf-embed-bad-relationship ---
+// Error: 50-55 expected "source", "data", "alternative", "supplement", or none
+#pdf.embed("/assets/data/zoo.csv", relationship: "foo")
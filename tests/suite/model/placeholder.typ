@@ -0,0 +1,11 @@
+// Test placeholders.
+
+--- placeholder-query ---
+#{
+  placeholder("serial", width: 2cm, height: 0pt)
+  context test(query(placeholder).map(it => it.name), ("serial",))
+}
+
+--- placeholder-missing-name ---
+// Error: 2-15 missing argument: name
+#placeholder()
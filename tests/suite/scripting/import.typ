@@ -225,6 +225,13 @@ This is never reached.
 // Error: 7-12 unknown variable: chap1
 #test(chap1.b, "Klaus")
 
+--- import-reexport ---
+// Imported items become part of the importing module and are thus re-exported.
+#import "./modules/reexport.typ": add, b, chap1
+#test(add(1, 2), 3)
+#test(b, 1)
+#test(chap1.name, "Klaus")
+
 --- import-incomplete ---
 // Error: 8 expected expression
 #import
@@ -0,0 +1,4 @@
+// SKIP
+// A module that re-exports items from another module.
+#import "../module.typ": item as add, b
+#import "chap1.typ"
@@ -157,6 +157,10 @@
 #test(1 == 1.0, true)
 #test(30% == 30% + 0cm, true)
 #test(1in == 0% + 72pt, true)
+#test(1in == 2.54cm, true)
+#test(1in == 25.4mm, true)
+#test(10mm == 1cm, true)
+#test(3mm == 0.3cm, true)
 #test(30% == 30% + 1cm, false)
 #test("ab" == "a" + "b", true)
 #test(() == (1,), false)
@@ -60,6 +60,23 @@
   f(1, 2)
 }
 
+--- params-mixed ---
+// Test destructuring, named, and sink parameters in one closure.
+#let f((x, y), (name: n), scale: 1, ..rest) = (
+  (x + y) * scale,
+  n,
+  rest.pos(),
+  rest.named(),
+)
+#test(f((1, 2), (name: "a")), (3, "a", (), (:)))
+#test(f((1, 2), (name: "a"), 3, 4, scale: 2, extra: true), (6, "a", (3, 4), (extra: true)))
+#test(f(..((1, 2), (name: "b")), scale: 10), (30, "b", (), (:)))
+
+--- params-destructuring-mismatch ---
+// Error: 8-14 too many elements to destructure
+#let f((a, b)) = a + b
+#f((1, 2, 3))
+
 --- issue-1029-parameter-destructuring ---
 // Test that underscore works in parameter patterns.
 #test((1, 2, 3).zip((1, 2, 3)).map(((_, x)) => x), (1, 2, 3))
@@ -57,6 +57,18 @@ Hello *#x*
 
 @hello from the @unknown
 
+--- set-if-scoped ---
+// Test that a conditional set rule only applies to its block.
+#let draft = true
+#{
+  {
+    set text(size: 20pt) if draft
+    set text(size: 30pt) if not draft
+    context test(text.size, 20pt)
+  }
+  context test(text.size, 10pt)
+}
+
 --- set-if-bad-type ---
 // Error: 19-24 expected boolean, found integer
 #set text(red) if 1 + 2
@@ -75,6 +75,15 @@ Rust is memory-safe and blazingly fast. Let's rewrite everything in rust.
 #show "hello": it => it.text.split("").map(upper).join("|")
 Oh, hello there!
 
+--- show-text-regex-transform ---
+// Test transforming the matched text with a function.
+#show regex("\d{4}-\d{2}-\d{2}"): it => {
+  let (year, month, day) = it.text.split("-").map(int)
+  test(datetime(year: year, month: month, day: day).display(), it.text)
+}
+
+2024-03-01
+
 --- show-text-in-other-show ---
 // Replace worlds but only in lists.
 #show list: it => [
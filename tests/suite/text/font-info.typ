@@ -0,0 +1,17 @@
+--- font-info ---
+#let info = font-info("Linux Libertine", covers: "Typst")
+#test(info.family, "Linux Libertine")
+#test(info.covers, true)
+#test(info.variants.contains((style: "normal", weight: "regular", stretch: 100%)), true)
+#test(type(info.features), array)
+#test("latn" in info.scripts, true)
+
+--- font-info-case-insensitive ---
+#test(font-info("LINUX libertine").family, "Linux Libertine")
+
+--- font-info-coverage ---
+#test(font-info("Linux Libertine", covers: "😀").covers, false)
+#test("covers" in font-info("Linux Libertine"), false)
+
+--- font-info-unknown ---
+#test(font-info("Nonexistent"), none)
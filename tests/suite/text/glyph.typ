@@ -0,0 +1,12 @@
+--- glyph-inline ---
+// Test glyphs within a paragraph, styled like the surrounding text.
+The letter #glyph(36, font: "Linux Libertine", alt: "A") by index and
+#text(fill: blue, size: 14pt, glyph(37, alt: "B")) in another style.
+
+--- glyph-index-out-of-range ---
+// Error: 2-14 font does not contain a glyph with index 65535
+#glyph(65535)
+
+--- glyph-font-not-found ---
+// Error: 2-31 no font could be found
+#glyph(1, font: "Nonexistent")
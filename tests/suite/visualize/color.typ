@@ -329,3 +329,15 @@
 #color.hsl(blue) \
 #color.hsv(blue) \
 #luma(blue)
+
+--- color-contrast-warning-page ---
+#set page(fill: rgb("222222"))
+// Warning: 1-5 text has too little contrast with its background
+// Hint: 1-5 the contrast ratio is 1.32:1, but at least 3:1 is needed for the text to be readable
+Dark
+
+--- color-contrast-warning-block ---
+// Warning: 34-42 text has too little contrast with its background
+// Hint: 34-42 the contrast ratio is 1.36:1, but at least 3:1 is needed for the text to be readable
+#block(fill: yellow, text(white)[Too pale])
+#block(fill: navy, text(white)[Readable])
@@ -104,6 +104,41 @@ A #box(image("/assets/images/tiger.jpg", height: 1cm, width: 80%)) B
 // Error: 2-91 failed to decode image (Format error decoding Png: Invalid PNG signature.)
 #image.decode(read("/assets/images/tiger.jpg", encoding: none), format: "png", width: 80%)
 
+--- image-crop ---
+// Crop a four-colored square down to parts of its quadrants.
+#let quads = image.decode.with(
+  `<svg xmlns="http://www.w3.org/2000/svg" width="20" height="20">
+    <rect width="10" height="10" fill="red"/>
+    <rect x="10" width="10" height="10" fill="green"/>
+    <rect y="10" width="10" height="10" fill="blue"/>
+    <rect x="10" y="10" width="10" height="10" fill="orange"/>
+  </svg>`.text,
+  format: "svg",
+  width: 20pt,
+)
+#stack(
+  dir: ltr,
+  spacing: 4pt,
+  quads(),
+  quads(crop: (left: 50%)),
+  quads(crop: (top: 50%, right: 50%)),
+  quads(crop: (x: 25%, y: 25%)),
+)
+
+--- image-crop-bad ---
+// Error: 2-51 cropping must leave part of the image visible
+#image("/assets/images/tiger.jpg", crop: (x: 50%))
+
+--- image-info ---
+#let info = image.info("/assets/images/tiger.jpg")
+#test(info.format, "jpg")
+#test(type(info.width), float)
+#test(info.width > 0, true)
+
+--- image-info-file-not-found ---
+// Error: 13-34 file not found (searched at tests/suite/visualize/path/does/not/exist)
+#image.info("path/does/not/exist")
+
 --- issue-870-image-rotation ---
 // Ensure that EXIF rotation is applied.
 // https://github.com/image-rs/image/issues/1045